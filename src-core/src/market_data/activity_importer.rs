@@ -0,0 +1,475 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use super::market_data_errors::MarketDataError;
+use super::providers::market_data_provider::AssetProfiler;
+
+/// The kind of brokerage event an `ActivityImporter` can hand back. Mirrors
+/// the categories a double-entry ledger needs to post distinct account
+/// pairs for, rather than a single generic "transaction" bucket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActivityType {
+    Buy,
+    Sell,
+    Dividend,
+    Deposit,
+    Withdrawal,
+    Fee,
+}
+
+/// One brokerage event pulled from an importer, already normalized to the
+/// fields a ledger posting or a portfolio reconciliation needs.
+#[derive(Debug, Clone)]
+pub struct ImportedActivity {
+    pub account_id: String,
+    pub activity_type: ActivityType,
+    pub date: NaiveDate,
+    /// Empty for account-level events like deposits/withdrawals.
+    pub symbol: String,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub fee: Decimal,
+    pub currency: String,
+}
+
+/// A source of executed trades, dividends, deposits, fees, and other
+/// account activity for a date range, analogous to `MarketDataProvider`
+/// but for brokerage events instead of quotes.
+///
+/// No Tauri command or activities-domain service calls this yet — it's
+/// staged ahead of a future brokerage-import feature, not wired into a
+/// live import path today.
+#[async_trait]
+pub trait ActivityImporter: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn fetch_activities(
+        &self,
+        account_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ImportedActivity>, MarketDataError>;
+}
+
+const ALPACA_DEFAULT_BASE_URL: &str = "https://api.alpaca.markets";
+const ALPACA_PAGE_SIZE: usize = 100;
+
+/// Pages through Alpaca's `/v2/account/activities` endpoint and maps each
+/// record to an [`ImportedActivity`], resolving each symbol's settlement
+/// currency through the registered `AssetProfiler` rather than assuming
+/// everything settles in USD.
+pub struct AlpacaActivityImporter {
+    client: Client,
+    base_url: String,
+    api_key_id: String,
+    api_secret_key: String,
+    asset_profiler: Arc<dyn AssetProfiler + Send + Sync>,
+}
+
+impl AlpacaActivityImporter {
+    pub fn new(
+        api_key_id: String,
+        api_secret_key: String,
+        asset_profiler: Arc<dyn AssetProfiler + Send + Sync>,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: ALPACA_DEFAULT_BASE_URL.to_string(),
+            api_key_id,
+            api_secret_key,
+            asset_profiler,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    async fn currency_for_symbol(&self, symbol: &str) -> String {
+        self.asset_profiler
+            .get_asset_profile(symbol)
+            .await
+            .map(|profile| profile.currency)
+            .unwrap_or_else(|_| "USD".to_string())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AlpacaActivity {
+    id: String,
+    activity_type: String,
+    symbol: Option<String>,
+    qty: Option<String>,
+    price: Option<String>,
+    /// Alpaca's non-trade-activity dollar amount field — unset for `FILL`
+    /// records (which carry `qty`/`price` instead), populated for `FEE`
+    /// (and other NTAs) with the charge, which Alpaca reports as a debit
+    /// (negative).
+    net_amount: Option<String>,
+    date: String,
+}
+
+#[async_trait]
+impl ActivityImporter for AlpacaActivityImporter {
+    fn name(&self) -> &'static str {
+        "Alpaca"
+    }
+
+    async fn fetch_activities(
+        &self,
+        account_id: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ImportedActivity>, MarketDataError> {
+        let mut activities = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(format!("{}/v2/account/activities", self.base_url))
+                .header("APCA-API-KEY-ID", &self.api_key_id)
+                .header("APCA-API-SECRET-KEY", &self.api_secret_key)
+                .query(&[
+                    ("after", start.format("%Y-%m-%d").to_string()),
+                    ("until", end.format("%Y-%m-%d").to_string()),
+                    ("page_size", ALPACA_PAGE_SIZE.to_string()),
+                ]);
+
+            if let Some(token) = &page_token {
+                request = request.query(&[("page_token", token)]);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| MarketDataError::ProviderError(format!("Alpaca API error: {}", e)))?;
+
+            if !response.status().is_success() {
+                let body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                return Err(MarketDataError::ProviderError(format!("Alpaca API error: {}", body)));
+            }
+
+            let page: Vec<AlpacaActivity> = response
+                .json()
+                .await
+                .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse Alpaca response: {}", e)))?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            let is_last_page = page.len() < ALPACA_PAGE_SIZE;
+            page_token = page.last().map(|activity| activity.id.clone());
+
+            for record in page {
+                if let Some(activity) = self.map_activity(account_id, record).await {
+                    activities.push(activity);
+                }
+            }
+
+            if is_last_page {
+                break;
+            }
+        }
+
+        Ok(activities)
+    }
+}
+
+impl AlpacaActivityImporter {
+    async fn map_activity(&self, account_id: &str, record: AlpacaActivity) -> Option<ImportedActivity> {
+        let date = NaiveDate::parse_from_str(&record.date, "%Y-%m-%d").ok()?;
+        let parse_decimal = |value: &Option<String>| -> Decimal {
+            value
+                .as_deref()
+                .and_then(|v| v.parse::<Decimal>().ok())
+                .unwrap_or_default()
+        };
+
+        let activity_type = match record.activity_type.as_str() {
+            "FILL" => {
+                let quantity = parse_decimal(&record.qty);
+                if quantity < Decimal::ZERO {
+                    ActivityType::Sell
+                } else {
+                    ActivityType::Buy
+                }
+            }
+            "DIV" => ActivityType::Dividend,
+            "CSD" => ActivityType::Deposit,
+            "CSW" => ActivityType::Withdrawal,
+            "FEE" => ActivityType::Fee,
+            _ => return None,
+        };
+
+        let symbol = record.symbol.clone().unwrap_or_default();
+        let currency = if symbol.is_empty() {
+            "USD".to_string()
+        } else {
+            self.currency_for_symbol(&symbol).await
+        };
+
+        // Only `FEE` records carry a fee; `net_amount` is Alpaca's
+        // non-trade-activity amount field and comes back as a debit
+        // (negative), so take its magnitude.
+        let fee = if activity_type == ActivityType::Fee {
+            parse_decimal(&record.net_amount).abs()
+        } else {
+            Decimal::ZERO
+        };
+
+        Some(ImportedActivity {
+            account_id: account_id.to_string(),
+            activity_type,
+            date,
+            symbol,
+            quantity: parse_decimal(&record.qty).abs(),
+            price: parse_decimal(&record.price),
+            fee,
+            currency,
+        })
+    }
+}
+
+/// Renders activities as Ledger-CLI style plain-text journal entries: a
+/// dated header per transaction followed by postings that net to zero
+/// across an asset/cash leg and an income/expense leg.
+pub struct LedgerExporter;
+
+impl LedgerExporter {
+    pub fn export(activities: &[ImportedActivity]) -> String {
+        let mut journal = String::new();
+
+        for activity in activities {
+            journal.push_str(&Self::render_entry(activity));
+            journal.push('\n');
+        }
+
+        journal
+    }
+
+    fn render_entry(activity: &ImportedActivity) -> String {
+        let account = &activity.account_id;
+        let gross = activity.quantity * activity.price;
+        let amount = gross + activity.fee;
+        let proceeds = gross - activity.fee;
+
+        match activity.activity_type {
+            ActivityType::Buy => format!(
+                "{date} * Buy {symbol}\n    Assets:{account}:{symbol}    {qty} {symbol} @ {price} {currency}\n    Expenses:Fees    {fee} {currency}\n    Assets:{account}:Cash    -{amount} {currency}\n",
+                date = activity.date,
+                symbol = activity.symbol,
+                account = account,
+                qty = activity.quantity,
+                price = activity.price,
+                fee = activity.fee,
+                currency = activity.currency,
+                amount = amount,
+            ),
+            ActivityType::Sell => format!(
+                "{date} * Sell {symbol}\n    Assets:{account}:Cash    {amount} {currency}\n    Expenses:Fees    {fee} {currency}\n    Assets:{account}:{symbol}    -{qty} {symbol} @ {price} {currency}\n",
+                date = activity.date,
+                symbol = activity.symbol,
+                account = account,
+                qty = activity.quantity,
+                price = activity.price,
+                fee = activity.fee,
+                currency = activity.currency,
+                amount = proceeds,
+            ),
+            ActivityType::Dividend => format!(
+                "{date} * Dividend {symbol}\n    Assets:{account}:Cash    {amount} {currency}\n    Income:Dividends:{symbol}    -{amount} {currency}\n",
+                date = activity.date,
+                symbol = activity.symbol,
+                account = account,
+                currency = activity.currency,
+                amount = activity.price,
+            ),
+            ActivityType::Deposit => format!(
+                "{date} * Deposit\n    Assets:{account}:Cash    {amount} {currency}\n    Equity:Deposits    -{amount} {currency}\n",
+                date = activity.date,
+                account = account,
+                currency = activity.currency,
+                amount = activity.price,
+            ),
+            ActivityType::Withdrawal => format!(
+                "{date} * Withdrawal\n    Equity:Withdrawals    {amount} {currency}\n    Assets:{account}:Cash    -{amount} {currency}\n",
+                date = activity.date,
+                account = account,
+                currency = activity.currency,
+                amount = activity.price,
+            ),
+            ActivityType::Fee => format!(
+                "{date} * Fee\n    Expenses:Fees    {amount} {currency}\n    Assets:{account}:Cash    -{amount} {currency}\n",
+                date = activity.date,
+                account = account,
+                currency = activity.currency,
+                amount = activity.fee,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::providers::models::AssetProfile;
+    use crate::market_data::QuoteSummary;
+
+    struct FixedCurrencyProfiler(String);
+
+    #[async_trait]
+    impl AssetProfiler for FixedCurrencyProfiler {
+        async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+            Ok(AssetProfile {
+                id: None,
+                isin: None,
+                symbol: symbol.to_string(),
+                symbol_mapping: None,
+                name: None,
+                asset_type: None,
+                asset_class: None,
+                asset_sub_class: None,
+                currency: self.0.clone(),
+                data_source: "TEST".to_string(),
+                notes: None,
+                countries: None,
+                categories: None,
+                classes: None,
+                attributes: None,
+                sectors: None,
+                url: None,
+            })
+        }
+
+        async fn search_ticker(&self, _query: &str) -> Result<Vec<QuoteSummary>, MarketDataError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn importer(currency: &str) -> AlpacaActivityImporter {
+        AlpacaActivityImporter::new(
+            "key".to_string(),
+            "secret".to_string(),
+            Arc::new(FixedCurrencyProfiler(currency.to_string())),
+        )
+    }
+
+    fn raw_activity(activity_type: &str, symbol: Option<&str>, qty: Option<&str>, price: Option<&str>) -> AlpacaActivity {
+        raw_activity_with_net_amount(activity_type, symbol, qty, price, None)
+    }
+
+    fn raw_activity_with_net_amount(
+        activity_type: &str,
+        symbol: Option<&str>,
+        qty: Option<&str>,
+        price: Option<&str>,
+        net_amount: Option<&str>,
+    ) -> AlpacaActivity {
+        AlpacaActivity {
+            id: "1".to_string(),
+            activity_type: activity_type.to_string(),
+            symbol: symbol.map(str::to_string),
+            qty: qty.map(str::to_string),
+            price: price.map(str::to_string),
+            net_amount: net_amount.map(str::to_string),
+            date: "2026-07-24".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn positive_fill_quantity_maps_to_a_buy() {
+        let activity = importer("USD")
+            .map_activity("acct-1", raw_activity("FILL", Some("AAPL"), Some("10"), Some("150")))
+            .await
+            .unwrap();
+        assert_eq!(activity.activity_type, ActivityType::Buy);
+        assert_eq!(activity.quantity, Decimal::from(10));
+        assert_eq!(activity.currency, "USD");
+    }
+
+    #[tokio::test]
+    async fn negative_fill_quantity_maps_to_a_sell_with_a_positive_quantity() {
+        let activity = importer("USD")
+            .map_activity("acct-1", raw_activity("FILL", Some("AAPL"), Some("-10"), Some("150")))
+            .await
+            .unwrap();
+        assert_eq!(activity.activity_type, ActivityType::Sell);
+        assert_eq!(activity.quantity, Decimal::from(10));
+    }
+
+    #[tokio::test]
+    async fn account_level_events_default_to_usd_without_a_symbol_lookup() {
+        let activity = importer("EUR")
+            .map_activity("acct-1", raw_activity("CSD", None, None, Some("500")))
+            .await
+            .unwrap();
+        assert_eq!(activity.activity_type, ActivityType::Deposit);
+        assert_eq!(activity.currency, "USD");
+    }
+
+    #[tokio::test]
+    async fn unrecognized_activity_types_are_skipped() {
+        assert!(importer("USD")
+            .map_activity("acct-1", raw_activity("UNKNOWN", None, None, None))
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn fee_activity_populates_fee_from_the_net_amount_magnitude() {
+        let activity = importer("USD")
+            .map_activity(
+                "acct-1",
+                raw_activity_with_net_amount("FEE", None, None, None, Some("-2.50")),
+            )
+            .await
+            .unwrap();
+        assert_eq!(activity.activity_type, ActivityType::Fee);
+        assert_eq!(activity.fee, Decimal::new(250, 2));
+    }
+
+    #[test]
+    fn ledger_export_nets_fee_postings_to_zero() {
+        let activity = ImportedActivity {
+            account_id: "acct-1".to_string(),
+            activity_type: ActivityType::Fee,
+            date: NaiveDate::parse_from_str("2026-07-24", "%Y-%m-%d").unwrap(),
+            symbol: String::new(),
+            quantity: Decimal::ZERO,
+            price: Decimal::ZERO,
+            fee: Decimal::new(250, 2),
+            currency: "USD".to_string(),
+        };
+
+        let journal = LedgerExporter::export(std::slice::from_ref(&activity));
+        assert!(journal.contains("Expenses:Fees    2.50 USD"));
+        assert!(journal.contains("Assets:acct-1:Cash    -2.50 USD"));
+    }
+
+    #[test]
+    fn ledger_export_nets_buy_postings_to_zero() {
+        let activity = ImportedActivity {
+            account_id: "acct-1".to_string(),
+            activity_type: ActivityType::Buy,
+            date: NaiveDate::parse_from_str("2026-07-24", "%Y-%m-%d").unwrap(),
+            symbol: "AAPL".to_string(),
+            quantity: Decimal::from(10),
+            price: Decimal::from(150),
+            fee: Decimal::ZERO,
+            currency: "USD".to_string(),
+        };
+
+        let journal = LedgerExporter::export(std::slice::from_ref(&activity));
+        assert!(journal.contains("Buy AAPL"));
+        assert!(journal.contains("Assets:acct-1:AAPL"));
+        assert!(journal.contains("-1500"));
+    }
+}
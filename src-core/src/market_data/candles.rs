@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+
+use super::market_data_model::Quote;
+
+/// Which day a `Weekly` bucket starts on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeekBoundary {
+    /// ISO-8601 week: Monday through Sunday.
+    IsoMonday,
+    /// Sunday through Saturday.
+    SundayStart,
+}
+
+/// Coarser-than-daily aggregation period for `MarketDataService::get_candles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandleInterval {
+    Weekly(WeekBoundary),
+    Monthly,
+    Quarterly,
+}
+
+/// Rolls per-day `quotes` up into coarser OHLCV candles: `open` comes from
+/// the first quote in each bucket, `close`/`adjclose` from the last, `high`
+/// is the bucket's max high, `low` its min low, and `volume` the bucket's
+/// sum. Buckets with no underlying quotes are skipped entirely rather than
+/// forward-filled. `quotes` need not be pre-sorted.
+pub fn resample_into_candles(quotes: &[Quote], interval: CandleInterval) -> Vec<Quote> {
+    let mut buckets: BTreeMap<NaiveDate, Vec<&Quote>> = BTreeMap::new();
+    for quote in quotes {
+        buckets
+            .entry(bucket_start(quote.timestamp.date_naive(), interval))
+            .or_default()
+            .push(quote);
+    }
+
+    buckets
+        .into_iter()
+        .map(|(start, mut bucket)| {
+            bucket.sort_by_key(|quote| quote.timestamp);
+            let first = bucket.first().expect("bucket is never empty");
+            let last = bucket.last().expect("bucket is never empty");
+            Quote {
+                id: format!("{}_{}", first.symbol, start.format("%Y%m%d")),
+                symbol: first.symbol.clone(),
+                timestamp: last.timestamp,
+                open: first.open,
+                high: bucket.iter().map(|quote| quote.high).max().unwrap_or(first.high),
+                low: bucket.iter().map(|quote| quote.low).min().unwrap_or(first.low),
+                close: last.close,
+                adjclose: last.adjclose,
+                volume: bucket.iter().map(|quote| quote.volume).sum(),
+                currency: first.currency.clone(),
+                data_source: first.data_source.clone(),
+                created_at: Utc::now(),
+            }
+        })
+        .collect()
+}
+
+fn bucket_start(date: NaiveDate, interval: CandleInterval) -> NaiveDate {
+    match interval {
+        CandleInterval::Weekly(WeekBoundary::IsoMonday) => {
+            date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64)
+        }
+        CandleInterval::Weekly(WeekBoundary::SundayStart) => {
+            date - chrono::Duration::days(date.weekday().num_days_from_sunday() as i64)
+        }
+        CandleInterval::Monthly => NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap_or(date),
+        CandleInterval::Quarterly => {
+            let quarter_start_month = (date.month0() / 3) * 3 + 1;
+            NaiveDate::from_ymd_opt(date.year(), quarter_start_month, 1).unwrap_or(date)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::market_data_model::DataSource;
+    use rust_decimal::Decimal;
+
+    fn d(value: i64) -> Decimal {
+        Decimal::from(value)
+    }
+
+    fn quote(date_str: &str, open: Decimal, high: Decimal, low: Decimal, close: Decimal, volume: Decimal) -> Quote {
+        let date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").unwrap();
+        let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
+        Quote {
+            id: format!("TEST_{}", date_str),
+            symbol: "TEST".to_string(),
+            timestamp,
+            open,
+            high,
+            low,
+            close,
+            adjclose: close,
+            volume,
+            currency: "USD".to_string(),
+            data_source: DataSource::Manual,
+            created_at: timestamp,
+        }
+    }
+
+    #[test]
+    fn weekly_iso_monday_bucket_starts_on_monday() {
+        // 2026-07-24 is a Friday.
+        let friday = NaiveDate::parse_from_str("2026-07-24", "%Y-%m-%d").unwrap();
+        let monday = NaiveDate::parse_from_str("2026-07-20", "%Y-%m-%d").unwrap();
+        assert_eq!(bucket_start(friday, CandleInterval::Weekly(WeekBoundary::IsoMonday)), monday);
+    }
+
+    #[test]
+    fn weekly_sunday_start_bucket_starts_on_sunday() {
+        let friday = NaiveDate::parse_from_str("2026-07-24", "%Y-%m-%d").unwrap();
+        let sunday = NaiveDate::parse_from_str("2026-07-19", "%Y-%m-%d").unwrap();
+        assert_eq!(bucket_start(friday, CandleInterval::Weekly(WeekBoundary::SundayStart)), sunday);
+    }
+
+    #[test]
+    fn monthly_and_quarterly_buckets_start_on_the_first() {
+        let mid_month = NaiveDate::parse_from_str("2026-08-15", "%Y-%m-%d").unwrap();
+        assert_eq!(
+            bucket_start(mid_month, CandleInterval::Monthly),
+            NaiveDate::parse_from_str("2026-08-01", "%Y-%m-%d").unwrap()
+        );
+        // August falls in Q3 (Jul-Sep), so the quarter starts in July.
+        assert_eq!(
+            bucket_start(mid_month, CandleInterval::Quarterly),
+            NaiveDate::parse_from_str("2026-07-01", "%Y-%m-%d").unwrap()
+        );
+    }
+
+    #[test]
+    fn resample_aggregates_open_high_low_close_volume_across_a_bucket() {
+        let quotes = vec![
+            quote("2026-07-20", d(100), d(105), d(99), d(102), d(10)),
+            quote("2026-07-22", d(102), d(110), d(101), d(108), d(20)),
+            quote("2026-07-24", d(108), d(109), d(95), d(97), d(15)),
+        ];
+
+        let candles = resample_into_candles(&quotes, CandleInterval::Weekly(WeekBoundary::IsoMonday));
+
+        assert_eq!(candles.len(), 1);
+        let candle = &candles[0];
+        assert_eq!(candle.open, d(100)); // from the first quote in the bucket
+        assert_eq!(candle.close, d(97)); // from the last quote in the bucket
+        assert_eq!(candle.high, d(110)); // bucket max high
+        assert_eq!(candle.low, d(95)); // bucket min low
+        assert_eq!(candle.volume, d(45)); // summed
+    }
+
+    #[test]
+    fn resample_splits_quotes_spanning_multiple_buckets() {
+        let quotes = vec![
+            quote("2026-07-24", d(100), d(101), d(99), d(100), d(5)), // week of 7/20
+            quote("2026-07-27", d(100), d(102), d(98), d(101), d(5)), // week of 7/27
+        ];
+
+        let candles = resample_into_candles(&quotes, CandleInterval::Weekly(WeekBoundary::IsoMonday));
+        assert_eq!(candles.len(), 2);
+    }
+
+    #[test]
+    fn resample_of_empty_input_is_empty() {
+        assert!(resample_into_candles(&[], CandleInterval::Monthly).is_empty());
+    }
+}
@@ -0,0 +1,284 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An ISO 4217 currency code, with an `Other` escape hatch for codes the
+/// market data providers emit that aren't in this list (or aren't real
+/// currencies at all, like a crypto ticker). Carrying this as a typed
+/// value instead of a bare `String` lets the aggregation layer compare
+/// currencies exhaustively instead of by string equality.
+///
+/// Not yet used by `Quote`, `AssetProfile`, or `QuoteSummary`, which still
+/// carry currency as a bare `String` — those structs live outside this
+/// module and migrating their field types is a separate, larger change.
+/// This type is a standalone parsing/validation utility for now: providers
+/// can use `as_str()`/`FromStr` to canonicalize a code they read off the
+/// wire without committing the rest of the pipeline to the new type yet.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+    Vnd,
+    Cad,
+    Aud,
+    Chf,
+    Cny,
+    Hkd,
+    Sgd,
+    Other(String),
+}
+
+impl Currency {
+    /// Parses directly from the wire representation without allocating
+    /// for any of the known codes, matching case-insensitively on bytes.
+    fn from_code(code: &str) -> Self {
+        let mut buf = [0u8; 3];
+        let upper: &[u8] = if code.len() == buf.len() {
+            buf.copy_from_slice(code.as_bytes());
+            buf.make_ascii_uppercase();
+            &buf
+        } else {
+            code.as_bytes()
+        };
+        match upper {
+            b"USD" => Currency::Usd,
+            b"EUR" => Currency::Eur,
+            b"GBP" => Currency::Gbp,
+            b"JPY" => Currency::Jpy,
+            b"VND" => Currency::Vnd,
+            b"CAD" => Currency::Cad,
+            b"AUD" => Currency::Aud,
+            b"CHF" => Currency::Chf,
+            b"CNY" => Currency::Cny,
+            b"HKD" => Currency::Hkd,
+            b"SGD" => Currency::Sgd,
+            _ => Currency::Other(code.to_ascii_uppercase()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Jpy => "JPY",
+            Currency::Vnd => "VND",
+            Currency::Cad => "CAD",
+            Currency::Aud => "AUD",
+            Currency::Chf => "CHF",
+            Currency::Cny => "CNY",
+            Currency::Hkd => "HKD",
+            Currency::Sgd => "SGD",
+            Currency::Other(code) => code,
+        }
+    }
+}
+
+impl FromStr for Currency {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Currency::from_code(s))
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+struct CurrencyVisitor;
+
+impl Visitor<'_> for CurrencyVisitor {
+    type Value = Currency;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a currency code string, e.g. \"USD\"")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Currency::from_code(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let code = std::str::from_utf8(value).map_err(de::Error::custom)?;
+        Ok(Currency::from_code(code))
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CurrencyVisitor)
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// The exchange/venue a quote trades on. Like [`Currency`], this keeps an
+/// `Other` escape hatch so a provider's long-tail venue names don't force
+/// a match against every known exchange before they can be stored.
+///
+/// Also not yet consumed by `AssetProfile`/`QuoteSummary`'s `exchange:
+/// String` fields or the provider/registry layer, which still passes
+/// exchange hints around as `Option<String>` (see `QuoteQuery::Stock`'s
+/// `Vec<String>` of exchanges) — same standalone-utility scope as
+/// [`Currency`] above, not wired into the live asset-profile path.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Exchange {
+    Nyse,
+    Nasdaq,
+    Hose,
+    Hnx,
+    Upcom,
+    Lse,
+    Tse,
+    Other(String),
+}
+
+impl Exchange {
+    fn from_code(code: &str) -> Self {
+        match code.to_ascii_uppercase().as_bytes() {
+            b"NYSE" => Exchange::Nyse,
+            b"NASDAQ" => Exchange::Nasdaq,
+            b"HOSE" | b"HSX" => Exchange::Hose,
+            b"HNX" => Exchange::Hnx,
+            b"UPCOM" => Exchange::Upcom,
+            b"LSE" => Exchange::Lse,
+            b"TSE" => Exchange::Tse,
+            _ => Exchange::Other(code.to_ascii_uppercase()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Exchange::Nyse => "NYSE",
+            Exchange::Nasdaq => "NASDAQ",
+            Exchange::Hose => "HOSE",
+            Exchange::Hnx => "HNX",
+            Exchange::Upcom => "UPCOM",
+            Exchange::Lse => "LSE",
+            Exchange::Tse => "TSE",
+            Exchange::Other(code) => code,
+        }
+    }
+}
+
+impl FromStr for Exchange {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Exchange::from_code(s))
+    }
+}
+
+impl fmt::Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+struct ExchangeVisitor;
+
+impl Visitor<'_> for ExchangeVisitor {
+    type Value = Exchange;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an exchange code string, e.g. \"NASDAQ\"")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Exchange::from_code(value))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let code = std::str::from_utf8(value).map_err(de::Error::custom)?;
+        Ok(Exchange::from_code(code))
+    }
+}
+
+impl<'de> Deserialize<'de> for Exchange {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ExchangeVisitor)
+    }
+}
+
+impl Serialize for Exchange {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn currency_parses_known_codes_case_insensitively() {
+        assert_eq!(Currency::from_str("usd").unwrap(), Currency::Usd);
+        assert_eq!(Currency::from_str("VND").unwrap(), Currency::Vnd);
+    }
+
+    #[test]
+    fn currency_falls_back_to_other_for_unknown_codes() {
+        assert_eq!(Currency::from_str("btc").unwrap(), Currency::Other("BTC".to_string()));
+        assert_eq!(Currency::Other("BTC".to_string()).as_str(), "BTC");
+    }
+
+    #[test]
+    fn currency_round_trips_through_its_code_string() {
+        let currency = Currency::Eur;
+        assert_eq!(currency.as_str(), "EUR");
+        assert_eq!(Currency::from_str(currency.as_str()).unwrap(), currency);
+    }
+
+    #[test]
+    fn exchange_parses_known_codes_and_hsx_alias_for_hose() {
+        assert_eq!(Exchange::from_str("nasdaq").unwrap(), Exchange::Nasdaq);
+        assert_eq!(Exchange::from_str("HSX").unwrap(), Exchange::Hose);
+    }
+
+    #[test]
+    fn exchange_falls_back_to_other_for_unknown_codes() {
+        assert_eq!(Exchange::from_str("xyz").unwrap(), Exchange::Other("XYZ".to_string()));
+    }
+
+    #[test]
+    fn exchange_round_trips_through_its_code_string() {
+        let exchange = Exchange::Hnx;
+        assert_eq!(exchange.as_str(), "HNX");
+        assert_eq!(Exchange::from_str(exchange.as_str()).unwrap(), exchange);
+    }
+}
@@ -9,12 +9,22 @@ use std::time::SystemTime;
 use tokio::sync::RwLock;
 
 
+use super::candles::CandleInterval;
+use super::market_data_constants::{
+    DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_MARKET_DATA_APP, DATA_SOURCE_METAL_PRICE_API,
+};
 use super::market_data_model::{
     LatestQuotePair, MarketDataProviderInfo, MarketDataProviderSetting, Quote, QuoteRequest,
     QuoteSummary, UpdateMarketDataProviderSetting, QuoteImport, ImportValidationStatus, DataSource,
 };
 use super::market_data_traits::{MarketDataRepositoryTrait, MarketDataServiceTrait};
+use super::provider_config::ProviderConfig;
 use super::providers::models::AssetProfile;
+use super::market_data_sync_actor::{MarketDataSyncActor, SyncJob, SyncJobOutcome};
+use super::quote_cache::QuoteCache;
+use super::quote_query::QuoteQuery;
+use super::request_time::RequestTime;
+use super::retry::{retry_with_backoff, RetryConfig};
 
 use crate::assets::assets_traits::AssetRepositoryTrait;
 use crate::errors::Result;
@@ -24,25 +34,52 @@ use crate::settings::SettingsServiceTrait;
 
 const QUOTE_LOOKBACK_DAYS: i64 = 7;
 
+/// Providers that require an API key/token, as opposed to the keyless
+/// providers (Yahoo, VN_MARKET, Manual) whose endpoints are compiled in.
+/// Used to report `requires_credentials` in `get_provider_info`.
+const API_KEY_PROVIDER_IDS: &[&str] = &[
+    DATA_SOURCE_ALPHA_VANTAGE,
+    DATA_SOURCE_METAL_PRICE_API,
+    DATA_SOURCE_MARKET_DATA_APP,
+    "KU_COIN",
+];
+
 pub struct MarketDataService {
     settings_service: Option<Arc<dyn SettingsServiceTrait>>,
     provider_registry: Arc<RwLock<ProviderRegistry>>,
     repository: Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
     asset_repository: Arc<dyn AssetRepositoryTrait + Send + Sync>,
+    quote_cache: QuoteCache,
+    sync_actor: MarketDataSyncActor,
+    /// Per-provider base URL/API token/rate limit, set via
+    /// `set_provider_config`. Kept in memory here rather than the
+    /// repository since it mirrors secrets already resolved through
+    /// `SecretManager`, not a persisted domain record. `Arc`-wrapped so
+    /// `MarketDataSyncActor` can read the same map when enforcing
+    /// per-provider rate limits on its background jobs.
+    provider_configs: Arc<RwLock<HashMap<String, ProviderConfig>>>,
+    /// Retry tuning shared by every `retry_with_backoff` call this service
+    /// makes. Defaults to `RetryConfig::default()`; overridable via
+    /// `with_retry_config`. Reading this from `settings_service` instead
+    /// needs a getter on `SettingsServiceTrait` that doesn't exist in this
+    /// part of the tree yet (see `VnMarketProvider::with_max_quote_age_trading_days`
+    /// for the same gap), so for now this is configurable per instance but
+    /// not yet backed by a live user-facing setting.
+    retry_config: RetryConfig,
 }
 
 #[async_trait]
 impl MarketDataServiceTrait for MarketDataService {
     async fn search_symbol(&self, query: &str) -> Result<Vec<QuoteSummary>> {
-        self.provider_registry
-            .read()
-            .await
-            .search_ticker(query)
-            .await
-            .map_err(|e| {
-                error!("Failed to search symbol '{}': {}", query, e);
-                e.into()
-            })
+        let retry_config = self.retry_config;
+        retry_with_backoff(&format!("search_symbol('{}')", query), &retry_config, || async {
+            self.provider_registry.read().await.search_ticker(query).await
+        })
+        .await
+        .map_err(|e| {
+            error!("Failed to search symbol '{}': {}", query, e);
+            e.into()
+        })
     }
 
     async fn get_latest_quote(
@@ -66,84 +103,83 @@ impl MarketDataServiceTrait for MarketDataService {
         quote_requests: &[QuoteRequest],
     ) -> Result<Vec<Quote>> {
         let mut results = Vec::new();
-        let mut failed_requests = Vec::new();
-
-        // Group requests by provider for efficiency
-        let mut provider_requests: HashMap<String, Vec<QuoteRequest>> = HashMap::new();
+        let mut misses = Vec::new();
 
-        let provider_registry = self.provider_registry.read().await;
+        // Consult the cache first; an entry is served only while
+        // `QuoteCache::get_fresh` still counts it as fresh (by trading day
+        // for equities/funds, by TTL for `=X` forex pairs), so anything
+        // still fresh is served without touching the network.
         for request in quote_requests {
-            let provider = provider_registry
-                .get_provider_for_symbol(&request.symbol)
-                .await;
-
-            match provider {
-                Some(provider_name) => {
-                    provider_requests
-                        .entry(provider_name.to_string())
-                        .or_default()
-                        .push(request.clone());
-                }
-                None => {
-                    error!("No provider found for symbol: {}", request.symbol);
-                    failed_requests.push(request.clone());
-                }
+            match self.quote_cache.get_fresh(&request.symbol, &request.data_source).await {
+                Some(quote) => results.push(quote),
+                None => misses.push(request.clone()),
             }
         }
 
-        // Process requests for each provider
-        for (provider_name, requests) in provider_requests {
-            let provider = self.provider_registry.read().await.get_provider(&provider_name).await;
-            
-            if let Some(provider) = provider {
-                let start = SystemTime::now() - std::time::Duration::from_secs((QUOTE_LOOKBACK_DAYS * 24 * 60 * 60) as u64);
-                let end = SystemTime::now();
-
-                let symbols_with_currencies: Vec<(String, String, Option<String>)> = requests
-                    .iter()
-                    .map(|req| (req.symbol.clone(), req.currency.clone(), None))
-                    .collect();
+        if misses.is_empty() {
+            return Ok(results);
+        }
 
-                match provider
-                    .get_historical_quotes_bulk(&symbols_with_currencies, start, end)
-                    .await
-                {
-                    Ok((quotes, failed_symbols)) => {
-                        // Get the latest quote for each symbol
-                        let mut latest_quotes: HashMap<String, Quote> = HashMap::new();
-                        
-                        for quote in quotes {
-                            let entry = latest_quotes.entry(quote.symbol.clone());
-                            entry
-                                .and_modify(|existing| {
-                                    if quote.timestamp > existing.timestamp {
-                                        *existing = quote.clone();
-                                    }
-                                })
-                                .or_insert(quote);
+        // Walk each symbol's priority-ordered candidate providers,
+        // retrying only the symbols a candidate failed on against the
+        // next one, instead of failing the whole request the moment a
+        // single provider errors.
+        let mut candidates_by_symbol: HashMap<String, Vec<String>> = HashMap::new();
+        let mut attempt_by_symbol: HashMap<String, usize> = HashMap::new();
+        let mut failed_requests = Vec::new();
+        let mut pending = misses;
+
+        while !pending.is_empty() {
+            let mut unroutable = Vec::new();
+            {
+                let provider_registry = self.provider_registry.read().await;
+                for request in &pending {
+                    let candidates = candidates_by_symbol
+                        .entry(request.symbol.clone())
+                        .or_insert_with(|| {
+                            provider_registry.get_provider_candidates_for_symbol(&request.symbol)
+                        });
+                    let attempt = *attempt_by_symbol.get(&request.symbol).unwrap_or(&0);
+                    match candidates.get(attempt) {
+                        Some(provider_id) => {
+                            self.quote_cache.enqueue(provider_id, request.clone()).await;
+                        }
+                        None => {
+                            error!("No remaining provider candidates for symbol: {}", request.symbol);
+                            unroutable.push(request.clone());
                         }
-
-                        results.extend(latest_quotes.into_values());
-                        failed_requests.extend(
-                            failed_symbols
-                                .into_iter()
-                                .zip(requests.iter())
-                                .map(|((symbol, currency, _), req)| QuoteRequest {
-                                    symbol,
-                                    currency,
-                                    data_source: req.data_source.clone(),
-                                })
-                        );
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to get quotes from provider '{}': {}",
-                            provider_name, e
-                        );
-                        failed_requests.extend(requests.clone());
                     }
                 }
             }
+            failed_requests.extend(unroutable);
+
+            let (flushed, flush_failures) = self.flush().await;
+            for quote in &flushed {
+                debug!(
+                    "Symbol '{}' satisfied by provider/source '{:?}'",
+                    quote.symbol, quote.data_source
+                );
+            }
+            results.extend(flushed);
+
+            pending = Vec::new();
+            for request in flush_failures {
+                let attempt = attempt_by_symbol.entry(request.symbol.clone()).or_insert(0);
+                *attempt += 1;
+                let has_more_candidates = candidates_by_symbol
+                    .get(&request.symbol)
+                    .map(|candidates| *attempt < candidates.len())
+                    .unwrap_or(false);
+                if has_more_candidates {
+                    debug!(
+                        "Falling back to next candidate provider for symbol '{}'",
+                        request.symbol
+                    );
+                    pending.push(request);
+                } else {
+                    failed_requests.push(request);
+                }
+            }
         }
 
         if failed_requests.is_empty() {
@@ -199,6 +235,62 @@ impl MarketDataServiceTrait for MarketDataService {
         }
     }
 
+    async fn get_quote_as_of(
+        &self,
+        symbol: &str,
+        currency: &str,
+        at: RequestTime,
+    ) -> Result<Option<Quote>> {
+        if matches!(at, RequestTime::Latest) {
+            return self.get_latest_quote(symbol, currency).await;
+        }
+
+        // Resolve against each provider's stored historical quotes, in
+        // priority order. Only stored quotes are consulted: when none
+        // satisfies `at`, this returns `None` without querying a provider.
+        // Today's "latest quote" would otherwise be the wrong price for a
+        // backdated snapshot.
+        for provider_id in self.provider_registry.read().await.ordered_provider_ids() {
+            if let Some(quote) = self.repository.get_quote_as_of(symbol, &provider_id, at)? {
+                return Ok(Some(quote));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn get_quote_for_query(&self, query: QuoteQuery, currency: &str) -> Result<Option<Quote>> {
+        match &query {
+            QuoteQuery::Stock(symbol, _exchanges) => self.get_latest_quote(symbol, currency).await,
+            QuoteQuery::Forex(base, quote) => self.get_forex_quote(base, quote).await,
+        }
+    }
+
+    async fn get_exchange_rate(&self, base: &str, quote: &str, date: Option<NaiveDate>) -> Result<Decimal> {
+        if base.eq_ignore_ascii_case(quote) {
+            return Ok(Decimal::ONE);
+        }
+
+        let rate = match date {
+            Some(date) => {
+                let symbol = QuoteQuery::Forex(base.to_string(), quote.to_string()).symbol();
+                let at = date
+                    .and_hms_opt(0, 0, 0)
+                    .map(RequestTime::LastBefore)
+                    .unwrap_or(RequestTime::Latest);
+                self.get_quote_as_of(&symbol, quote, at).await?
+            }
+            None => self.get_forex_quote(base, quote).await?,
+        };
+
+        rate.map(|q| q.close).ok_or_else(|| {
+            crate::errors::Error::MarketData(crate::market_data::MarketDataError::NotFound(format!(
+                "{}{}",
+                base, quote
+            )))
+        })
+    }
+
     async fn get_asset_profile(&self, symbol: &str) -> Result<Option<AssetProfile>> {
         let provider_registry = self.provider_registry.read().await;
         let provider_name = provider_registry
@@ -215,7 +307,15 @@ impl MarketDataServiceTrait for MarketDataService {
                     .await;
 
                 if let Some(profiler) = profiler {
-                    match profiler.get_asset_profile(symbol).await {
+                    let retry_config = self.retry_config;
+                    let outcome = retry_with_backoff(
+                        &format!("get_asset_profile('{}')", symbol),
+                        &retry_config,
+                        || async { profiler.get_asset_profile(symbol).await },
+                    )
+                    .await;
+
+                    match outcome {
                         Ok(profile) => Ok(Some(profile)),
                         Err(crate::market_data::MarketDataError::NotFound(_)) => Ok(None),
                         Err(e) => {
@@ -365,6 +465,11 @@ impl MarketDataServiceTrait for MarketDataService {
     }
 
     async fn bulk_upsert_quotes(&self, quotes: Vec<Quote>) -> Result<usize> {
+        // Evict cached entries so manually imported prices win immediately
+        // instead of waiting out the cache TTL.
+        for quote in &quotes {
+            self.quote_cache.evict(&quote.symbol, &quote.data_source).await;
+        }
         self.repository.bulk_upsert_quotes(quotes).await
     }
 
@@ -468,25 +573,64 @@ impl MarketDataServiceTrait for MarketDataService {
 
     async fn get_provider_info(&self) -> Result<Vec<MarketDataProviderInfo>> {
         let providers = self.provider_registry.read().await.get_all_providers_with_ids().await;
+        let provider_configs = self.provider_configs.read().await;
         let mut info = Vec::new();
 
         for (id, provider) in providers {
+            let requires_credentials = API_KEY_PROVIDER_IDS.contains(&id.as_str());
+            let has_credentials = !requires_credentials
+                || provider_configs
+                    .get(&id)
+                    .and_then(|config| config.api_token.as_ref())
+                    .is_some_and(|token| !token.is_empty());
+
             let provider_info = MarketDataProviderInfo {
                 id: id.clone(),
                 name: provider.name().to_string(),
                 logo_filename: format!("{}.png", id.to_lowercase()),
                 last_synced_date: None,
+                requires_credentials,
+                has_credentials,
             };
             info.push(provider_info);
         }
 
         Ok(info)
     }
-    
+
+    async fn set_provider_config(&self, provider_id: String, config: ProviderConfig) -> Result<()> {
+        debug!("Setting provider config for {}", provider_id);
+        self.provider_registry
+            .write()
+            .await
+            .apply_provider_config(&provider_id, &config)
+            .await;
+        self.provider_configs.write().await.insert(provider_id, config);
+        Ok(())
+    }
+
+    async fn verify_providers(&self) -> Result<Vec<super::providers::provider_registry::ProviderHealth>> {
+        Ok(self.provider_registry.read().await.verify_all_providers().await)
+    }
+
+    async fn enqueue_sync_job(&self, job: SyncJob) -> Result<SyncJobOutcome> {
+        let receiver = self.sync_actor.enqueue(job).await;
+        receiver.await.map_err(|_| {
+            crate::errors::Error::MarketData(crate::market_data::MarketDataError::ProviderError(
+                "Sync actor dropped the job before it completed".to_string(),
+            ))
+        })
+    }
+
     async fn save_quote(&self, quote: &Quote) -> Result<Quote> {
+        self.quote_cache.evict(&quote.symbol, &quote.data_source).await;
         self.repository.save_quote(quote).await
     }
-    
+
+    async fn invalidate_quote_cache(&self, symbol: &str) {
+        self.quote_cache.invalidate_symbol(symbol).await;
+    }
+
     fn get_latest_quotes_pair_for_symbols(
         &self,
         symbol_source_pairs: &[(String, String)],
@@ -502,6 +646,18 @@ impl MarketDataServiceTrait for MarketDataService {
     ) -> Result<Vec<Quote>> {
         self.repository.get_historical_quotes_for_symbols_in_range(symbols, start_date, end_date)
     }
+
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        interval: CandleInterval,
+    ) -> Result<Vec<Quote>> {
+        let symbols = HashSet::from([symbol.to_string()]);
+        let quotes = self.get_historical_quotes_for_symbols_in_range(&symbols, start, end).await?;
+        Ok(super::candles::resample_into_candles(&quotes, interval))
+    }
 }
 
 impl MarketDataService {
@@ -511,11 +667,229 @@ impl MarketDataService {
         repository: Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
         asset_repository: Arc<dyn AssetRepositoryTrait + Send + Sync>,
     ) -> Self {
+        let provider_configs = Arc::new(RwLock::new(HashMap::new()));
+        let sync_actor = MarketDataSyncActor::spawn(
+            repository.clone(),
+            provider_registry.clone(),
+            provider_configs.clone(),
+        );
+
         Self {
             settings_service,
             provider_registry,
             repository,
             asset_repository,
+            quote_cache: QuoteCache::new(),
+            sync_actor,
+            provider_configs,
+            retry_config: RetryConfig::default(),
+        }
+    }
+
+    pub fn with_retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Dispatches every batch accumulated in `quote_cache` as one grouped
+    /// request per provider, running the providers concurrently with a
+    /// bounded join set so one slow or erroring provider never stalls the
+    /// others. Results are spliced back into the cache. Returns the quotes
+    /// resolved this way plus the requests no provider satisfied.
+    async fn flush(&self) -> (Vec<Quote>, Vec<QuoteRequest>) {
+        let batches = self.quote_cache.take_batches().await;
+        let mut join_set = tokio::task::JoinSet::new();
+
+        for (provider_id, requests) in batches {
+            let provider = self.provider_registry.read().await.get_provider(&provider_id).await;
+            join_set.spawn(Self::fetch_provider_batch(provider_id, provider, requests, self.retry_config));
+        }
+
+        let mut results = Vec::new();
+        let mut failed_requests = Vec::new();
+
+        while let Some(outcome) = join_set.join_next().await {
+            let (quotes, failed) = match outcome {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    error!("Provider fetch task panicked during fan-out: {}", e);
+                    continue;
+                }
+            };
+
+            self.quote_cache.store(&quotes).await;
+            results.extend(quotes);
+            failed_requests.extend(failed);
+        }
+
+        (results, failed_requests)
+    }
+
+    /// Fetches one provider's batch, retrying transient failures with
+    /// backoff (see [`retry_with_backoff`]), and returns the resolved
+    /// quotes (deduped to the latest per symbol) plus the requests that
+    /// provider failed on, exhausted retries on, or never returned a quote
+    /// for.
+    async fn fetch_provider_batch(
+        provider_id: String,
+        provider: Option<Arc<dyn crate::market_data::MarketDataProvider + Send + Sync>>,
+        requests: Vec<QuoteRequest>,
+        retry_config: RetryConfig,
+    ) -> (Vec<Quote>, Vec<QuoteRequest>) {
+        let Some(provider) = provider else {
+            return (Vec::new(), requests);
+        };
+
+        let start = SystemTime::now() - std::time::Duration::from_secs((QUOTE_LOOKBACK_DAYS * 24 * 60 * 60) as u64);
+        let end = SystemTime::now();
+
+        let symbols_with_currencies: Vec<(String, String, Option<String>)> = requests
+            .iter()
+            .map(|req| (req.symbol.clone(), req.currency.clone(), None))
+            .collect();
+
+        let outcome = retry_with_backoff(
+            &format!("get_historical_quotes_bulk(provider='{}')", provider_id),
+            &retry_config,
+            || provider.get_historical_quotes_bulk(&symbols_with_currencies, start, end),
+        )
+        .await;
+
+        match outcome {
+            Ok((quotes, failed_symbols)) => {
+                let mut latest_quotes: HashMap<String, Quote> = HashMap::new();
+                for quote in quotes {
+                    latest_quotes
+                        .entry(quote.symbol.clone())
+                        .and_modify(|existing| {
+                            if quote.timestamp > existing.timestamp {
+                                *existing = quote.clone();
+                            }
+                        })
+                        .or_insert(quote);
+                }
+
+                // Treat a quote older than the expected trading day as
+                // unresolved rather than silently handing back stale data,
+                // so a long provider outage shows up as a failed symbol
+                // instead of a quietly aging price.
+                let today = Utc::now().date_naive();
+                latest_quotes.retain(|symbol, quote| {
+                    let fresh = !super::staleness::is_outdated_quote(
+                        quote.timestamp.date_naive(),
+                        today,
+                        super::staleness::DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS,
+                        &HashSet::new(),
+                    );
+                    if !fresh {
+                        debug!("Dropping stale quote for '{}' dated {}", symbol, quote.timestamp.date_naive());
+                    }
+                    fresh
+                });
+
+                let failed_symbol_names: HashSet<&str> =
+                    failed_symbols.iter().map(|(symbol, _, _)| symbol.as_str()).collect();
+                let failed: Vec<QuoteRequest> = requests
+                    .into_iter()
+                    .filter(|req| {
+                        failed_symbol_names.contains(req.symbol.as_str())
+                            || !latest_quotes.contains_key(&req.symbol)
+                    })
+                    .collect();
+
+                (latest_quotes.into_values().collect(), failed)
+            }
+            Err(e) => {
+                error!("Failed to flush batch for provider '{}': {}", provider_id, e);
+                (Vec::new(), requests)
+            }
+        }
+    }
+
+    /// Resolves a forex pair, routing to a forex-capable provider when one
+    /// is registered, deriving the inverse rate when only the opposite
+    /// direction is quoted, and otherwise synthesizing the rate by
+    /// chaining two pairs through a common bridge currency (e.g.
+    /// A->USD->B when A->B isn't directly quoted by any provider).
+    async fn get_forex_quote(&self, base: &str, quote: &str) -> Result<Option<Quote>> {
+        const BRIDGE_CURRENCY: &str = "USD";
+
+        let query = QuoteQuery::Forex(base.to_string(), quote.to_string());
+        let has_direct_provider = self
+            .provider_registry
+            .read()
+            .await
+            .get_provider_for_query(&query)
+            .await
+            .is_some();
+
+        if has_direct_provider {
+            if let Some(direct) = self.get_latest_quote(&query.symbol(), quote).await? {
+                return Ok(Some(direct));
+            }
+        }
+
+        // No direct quote; see if the opposite direction is quoted and
+        // invert it (quote/base = 1 / (base/quote)).
+        let inverse_query = QuoteQuery::Forex(quote.to_string(), base.to_string());
+        let has_inverse_provider = self
+            .provider_registry
+            .read()
+            .await
+            .get_provider_for_query(&inverse_query)
+            .await
+            .is_some();
+
+        if has_inverse_provider {
+            if let Some(inverse) = self.get_latest_quote(&inverse_query.symbol(), base).await? {
+                if !inverse.close.is_zero() {
+                    let inverted = Decimal::ONE / inverse.close;
+                    return Ok(Some(Quote {
+                        id: format!("{}{}_{}", base, quote, inverse.timestamp.format("%Y%m%d")),
+                        symbol: query.symbol(),
+                        timestamp: inverse.timestamp,
+                        open: inverted,
+                        high: inverted,
+                        low: inverted,
+                        close: inverted,
+                        adjclose: inverted,
+                        volume: Decimal::ZERO,
+                        currency: quote.to_string(),
+                        data_source: inverse.data_source,
+                        created_at: Utc::now(),
+                    }));
+                }
+            }
+        }
+
+        if base == BRIDGE_CURRENCY || quote == BRIDGE_CURRENCY {
+            return Ok(None);
+        }
+
+        let (base_leg, quote_leg) = tokio::join!(
+            self.get_forex_quote(base, BRIDGE_CURRENCY),
+            self.get_forex_quote(BRIDGE_CURRENCY, quote)
+        );
+
+        match (base_leg?, quote_leg?) {
+            (Some(base_rate), Some(quote_rate)) => {
+                let cross_close = base_rate.close * quote_rate.close;
+                Ok(Some(Quote {
+                    id: format!("{}{}_{}", base, quote, base_rate.timestamp.format("%Y%m%d")),
+                    symbol: query.symbol(),
+                    timestamp: base_rate.timestamp,
+                    open: cross_close,
+                    high: cross_close,
+                    low: cross_close,
+                    close: cross_close,
+                    adjclose: cross_close,
+                    volume: rust_decimal::Decimal::ZERO,
+                    currency: quote.to_string(),
+                    data_source: base_rate.data_source,
+                    created_at: Utc::now(),
+                }))
+            }
+            _ => Ok(None),
         }
     }
 }
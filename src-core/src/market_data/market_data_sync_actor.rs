@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use log::{debug, error, info};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+
+use super::market_data_traits::MarketDataRepositoryTrait;
+use super::provider_config::{ProviderConfig, RateLimiterRegistry};
+use super::providers::provider_registry::ProviderRegistry;
+use super::quotes_coordinator::QuotesCoordinator;
+
+/// A unit of background work the sync actor can be asked to perform.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum SyncJob {
+    RefreshLatest(Vec<String>),
+    BackfillHistory { symbols: Vec<String>, range: (NaiveDate, NaiveDate) },
+    ReverifyProviders,
+}
+
+impl SyncJob {
+    /// Identifies overlapping work so two callers asking for the same
+    /// symbol/range don't both hit the network; collapsed into one
+    /// in-flight job.
+    fn dedup_key(&self) -> String {
+        match self {
+            SyncJob::RefreshLatest(symbols) => {
+                let mut symbols = symbols.clone();
+                symbols.sort();
+                format!("latest:{}", symbols.join(","))
+            }
+            SyncJob::BackfillHistory { symbols, range } => {
+                let mut symbols = symbols.clone();
+                symbols.sort();
+                format!("backfill:{}:{}-{}", symbols.join(","), range.0, range.1)
+            }
+            SyncJob::ReverifyProviders => "reverify".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub enum SyncJobOutcome {
+    Completed { symbols_synced: usize },
+    Failed { reason: String },
+}
+
+struct SyncRequest {
+    job: SyncJob,
+    reply: oneshot::Sender<SyncJobOutcome>,
+}
+
+/// Holds shared (`Arc`) handles to the market data repository and the
+/// provider registry, the same instances `MarketDataService` keeps using,
+/// and drains `SyncJob`s off an `mpsc` channel so large historical
+/// refreshes run on a background task instead of blocking callers. Follows
+/// the actor pattern of MeiliSearch's index-controller, but does not own
+/// its store exclusively.
+///
+/// Enforces per-provider rate limits (via the shared `RateLimiterRegistry`,
+/// consulted against the same `provider_configs` `set_provider_config`
+/// writes to) on top of the in-flight deduplication below, so a backlog of
+/// queued jobs can't burst past a provider's configured `requests_per_minute`
+/// just because they all landed on the actor at once.
+pub struct MarketDataSyncActor {
+    sender: mpsc::Sender<SyncRequest>,
+}
+
+impl MarketDataSyncActor {
+    /// Spawns the actor's event loop and returns a handle for enqueuing
+    /// jobs. The loop keeps its clones of `repository`/`provider_registry`
+    /// for its lifetime; the caller's own handles stay usable alongside it.
+    pub fn spawn(
+        repository: Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
+        provider_registry: Arc<RwLock<ProviderRegistry>>,
+        provider_configs: Arc<RwLock<HashMap<String, ProviderConfig>>>,
+    ) -> Self {
+        let rate_limiters = Arc::new(RateLimiterRegistry::new());
+        let quotes_coordinator = Arc::new(QuotesCoordinator::new(
+            provider_registry.clone(),
+            provider_configs.clone(),
+            rate_limiters.clone(),
+        ));
+        let (sender, mut receiver) = mpsc::channel::<SyncRequest>(256);
+        let in_flight: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+
+        tokio::spawn(async move {
+            while let Some(request) = receiver.recv().await {
+                let dedup_key = request.job.dedup_key();
+
+                {
+                    let mut in_flight = in_flight.lock().await;
+                    if !in_flight.insert(dedup_key.clone()) {
+                        debug!("Collapsing duplicate in-flight sync job: {}", dedup_key);
+                        let _ = request.reply.send(SyncJobOutcome::Completed { symbols_synced: 0 });
+                        continue;
+                    }
+                }
+
+                let repository = repository.clone();
+                let provider_registry = provider_registry.clone();
+                let provider_configs = provider_configs.clone();
+                let rate_limiters = rate_limiters.clone();
+                let quotes_coordinator = quotes_coordinator.clone();
+                let in_flight = in_flight.clone();
+
+                tokio::spawn(async move {
+                    let outcome = Self::run_job(
+                        &repository,
+                        &provider_registry,
+                        &provider_configs,
+                        &rate_limiters,
+                        &quotes_coordinator,
+                        request.job,
+                    )
+                    .await;
+                    in_flight.lock().await.remove(&dedup_key);
+                    let _ = request.reply.send(outcome);
+                });
+            }
+        });
+
+        Self { sender }
+    }
+
+    /// Enqueues a job and returns immediately; the caller can await the
+    /// returned receiver to observe completion without blocking the
+    /// actor's loop.
+    pub async fn enqueue(&self, job: SyncJob) -> oneshot::Receiver<SyncJobOutcome> {
+        let (reply, receiver) = oneshot::channel();
+        if self.sender.send(SyncRequest { job, reply }).await.is_err() {
+            error!("Market data sync actor has shut down; job dropped");
+        }
+        receiver
+    }
+
+    async fn run_job(
+        repository: &Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
+        provider_registry: &Arc<RwLock<ProviderRegistry>>,
+        provider_configs: &Arc<RwLock<HashMap<String, ProviderConfig>>>,
+        rate_limiters: &Arc<RateLimiterRegistry>,
+        quotes_coordinator: &Arc<QuotesCoordinator>,
+        job: SyncJob,
+    ) -> SyncJobOutcome {
+        match job {
+            SyncJob::RefreshLatest(symbols) => {
+                Self::refresh_latest(repository, provider_registry, provider_configs, rate_limiters, symbols).await
+            }
+            SyncJob::BackfillHistory { symbols, range } => {
+                Self::backfill_history(repository, quotes_coordinator, symbols, range).await
+            }
+            SyncJob::ReverifyProviders => {
+                let results = provider_registry.read().await.verify_all_providers().await;
+                let failures = results.iter().filter(|h| !h.healthy).count();
+                if failures == 0 {
+                    SyncJobOutcome::Completed { symbols_synced: results.len() }
+                } else {
+                    SyncJobOutcome::Failed {
+                        reason: format!("{} of {} providers failed verification", failures, results.len()),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn refresh_latest(
+        repository: &Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
+        provider_registry: &Arc<RwLock<ProviderRegistry>>,
+        provider_configs: &Arc<RwLock<HashMap<String, ProviderConfig>>>,
+        rate_limiters: &Arc<RateLimiterRegistry>,
+        symbols: Vec<String>,
+    ) -> SyncJobOutcome {
+        let mut synced = 0;
+        for symbol in symbols {
+            let provider_id = provider_registry.read().await.get_provider_for_symbol(&symbol).await.map(str::to_string);
+            let Some(provider_id) = provider_id else { continue };
+            let Some(provider) = provider_registry.read().await.get_provider(&provider_id).await else { continue };
+
+            rate_limiters.acquire(&provider_id, provider_configs).await;
+
+            let end = std::time::SystemTime::now();
+            let start = end - std::time::Duration::from_secs(7 * 24 * 60 * 60);
+            match provider.get_historical_quotes(&symbol, start, end, "USD".to_string()).await {
+                Ok(quotes) => {
+                    if let Err(e) = repository.save_quotes(&quotes).await {
+                        error!("Failed to persist refreshed quotes for '{}': {}", symbol, e);
+                        continue;
+                    }
+                    synced += 1;
+                }
+                Err(e) => error!("Failed to refresh latest quote for '{}': {}", symbol, e),
+            }
+        }
+
+        SyncJobOutcome::Completed { symbols_synced: synced }
+    }
+
+    async fn backfill_history(
+        repository: &Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
+        quotes_coordinator: &Arc<QuotesCoordinator>,
+        symbols: Vec<String>,
+        range: (NaiveDate, NaiveDate),
+    ) -> SyncJobOutcome {
+        // Resume incremental backfills from each source's last sync date
+        // instead of always re-fetching the full requested range.
+        let resume_points = match repository.get_latest_sync_dates_by_source() {
+            Ok(points) => points,
+            Err(e) => return SyncJobOutcome::Failed { reason: e.to_string() },
+        };
+        let earliest_resume = resume_points
+            .values()
+            .filter_map(|d| *d)
+            .map(|d| d.date())
+            .min()
+            .filter(|d| *d > range.0)
+            .unwrap_or(range.0);
+
+        let start = earliest_resume
+            .and_hms_opt(0, 0, 0)
+            .map(|naive| std::time::SystemTime::from(Utc.from_utc_datetime(&naive)))
+            .unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        let end = range
+            .1
+            .and_hms_opt(0, 0, 0)
+            .map(|naive| std::time::SystemTime::from(Utc.from_utc_datetime(&naive)))
+            .unwrap_or_else(std::time::SystemTime::now);
+
+        // `QuotesCoordinator` batches these by provider and falls through to
+        // the next provider in priority order for whatever the first one
+        // couldn't resolve, instead of giving up per symbol.
+        let symbols_with_currencies: Vec<(String, String)> =
+            symbols.iter().map(|symbol| (symbol.clone(), "USD".to_string())).collect();
+        let (quotes, unresolved) = quotes_coordinator
+            .get_historical_quotes_bulk(&symbols_with_currencies, start, end)
+            .await;
+
+        if !unresolved.is_empty() {
+            error!("No provider could backfill history for: {}", unresolved.join(", "));
+        }
+
+        if let Err(e) = repository.save_quotes(&quotes).await {
+            return SyncJobOutcome::Failed { reason: e.to_string() };
+        }
+
+        let synced = symbols.len() - unresolved.len();
+        info!("Backfill completed for {} symbols", synced);
+        SyncJobOutcome::Completed { symbols_synced: synced }
+    }
+}
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime};
+use rust_decimal::Decimal;
 use std::collections::{HashMap, HashSet};
 use std::time::SystemTime;
 
@@ -7,8 +8,14 @@ use crate::errors::Result;
 use crate::market_data::market_data_model::{
     MarketDataProviderSetting, UpdateMarketDataProviderSetting, QuoteRequest, ImportValidationStatus,
 };
+use super::candles::CandleInterval;
+use super::provider_config::ProviderConfig;
 use super::market_data_model::{Quote, QuoteSummary, LatestQuotePair, MarketDataProviderInfo, QuoteDb, QuoteImport};
+use super::market_data_sync_actor::{SyncJob, SyncJobOutcome};
 use super::providers::models::AssetProfile;
+use super::providers::provider_registry::ProviderHealth;
+use super::quote_query::QuoteQuery;
+use super::request_time::RequestTime;
 
 #[async_trait]
 pub trait MarketDataServiceTrait: Send + Sync {
@@ -40,11 +47,46 @@ pub trait MarketDataServiceTrait: Send + Sync {
         start_date: NaiveDate,
         end_date: NaiveDate,
     ) -> Result<Vec<Quote>>;
-    
+
+    /// Aggregates `symbol`'s stored daily quotes between `start` and `end`
+    /// into coarser OHLCV candles at `interval`. Buckets with no underlying
+    /// quotes are simply absent from the result, not forward-filled.
+    async fn get_candles(
+        &self,
+        symbol: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        interval: CandleInterval,
+    ) -> Result<Vec<Quote>>;
+
     async fn import_quotes(&self, quotes: Vec<QuoteImport>) -> Result<Vec<ImportValidationStatus>>;
-    
+
+    /// Resolves a quote as of a specific point in time rather than simply
+    /// the latest available price. Used for backdated portfolio snapshots
+    /// and reconciling transactions dated before today.
+    async fn get_quote_as_of(
+        &self,
+        symbol: &str,
+        currency: &str,
+        at: RequestTime,
+    ) -> Result<Option<Quote>>;
+
 
     async fn get_asset_profile(&self, symbol: &str) -> Result<Option<AssetProfile>>;
+
+    /// Resolves a typed query (equity symbol or forex pair) instead of a
+    /// bare symbol string, routing forex pairs to forex-capable providers
+    /// and synthesizing a cross-rate through a common currency when no
+    /// provider quotes the pair directly.
+    async fn get_quote_for_query(&self, query: QuoteQuery, currency: &str) -> Result<Option<Quote>>;
+
+    /// Resolves an FX rate for `base`/`quote`, optionally as of `date`
+    /// rather than the latest available rate. Without `date`, routes through
+    /// the same direct/inverse/bridge-currency resolution as
+    /// [`Self::get_quote_for_query`] with `QuoteQuery::Forex`. With `date`,
+    /// only a stored quote for the direct pair dated at or before the start
+    /// of that day is used; inverse and bridge-currency rates are not derived.
+    async fn get_exchange_rate(&self, base: &str, quote: &str, date: Option<NaiveDate>) -> Result<Decimal>;
     async fn validate_quote_import(&self, quote_import: &QuoteImport) -> ImportValidationStatus;
     
     async fn get_provider_settings(&self) -> Result<Vec<MarketDataProviderSetting>>;
@@ -56,11 +98,35 @@ pub trait MarketDataServiceTrait: Send + Sync {
     ) -> Result<()>;
     
     async fn get_provider_info(&self) -> Result<Vec<MarketDataProviderInfo>>;
+
+    /// Stores the base URL, API token, and rate limit for a configurable
+    /// provider (AlphaVantage, KuCoin, ...) and applies it to the registry
+    /// at once: a registered provider that supports runtime reconfiguration
+    /// (KuCoin) is rebuilt with it, other providers keep their current
+    /// instance. Reflected back through
+    /// `get_provider_info`'s `requires_credentials`/`has_credentials` fields.
+    async fn set_provider_config(&self, provider_id: String, config: ProviderConfig) -> Result<()>;
+
+    /// Runs each registered provider's contract-verification canary and
+    /// returns its health, so the settings UI can show which providers are
+    /// actually reachable/valid instead of assuming they work until a
+    /// user-facing query fails.
+    async fn verify_providers(&self) -> Result<Vec<ProviderHealth>>;
+
+    /// Hands a long-running refresh/backfill off to the background sync
+    /// actor instead of running it synchronously, so the UI can trigger
+    /// and monitor it without freezing. Resolves once the job completes.
+    async fn enqueue_sync_job(&self, job: SyncJob) -> Result<SyncJobOutcome>;
     
     async fn import_quotes_from_csv(&self, quotes: Vec<QuoteImport>, overwrite: bool) -> Result<Vec<QuoteImport>>;
     async fn bulk_upsert_quotes(&self, quotes: Vec<Quote>) -> Result<usize>;
     async fn save_quote(&self, quote: &Quote) -> Result<Quote>;
-    
+
+    /// Forces the next `get_latest_quote`/`get_latest_quotes_bulk` call for
+    /// `symbol` to bypass the in-memory cache and refetch from a provider,
+    /// regardless of which `DataSource` the cached entry was keyed under.
+    async fn invalidate_quote_cache(&self, symbol: &str);
+
     // Repository methods exposed through service
     fn get_latest_quotes_pair_for_symbols(
         &self,
@@ -72,6 +138,15 @@ pub trait MarketDataServiceTrait: Send + Sync {
 pub trait MarketDataRepositoryTrait {
     fn get_all_historical_quotes(&self) -> Result<Vec<Quote>>;
     fn get_historical_quotes_for_symbol(&self, symbol: &str, data_source: &str) -> Result<Vec<Quote>>;
+    /// Returns the stored quote for `symbol`/`source` that satisfies `at`
+    /// (`FirstAfter`/`LastBefore`), or the latest stored quote when `at`
+    /// is `RequestTime::Latest`.
+    fn get_quote_as_of(
+        &self,
+        symbol: &str,
+        source: &str,
+        at: RequestTime,
+    ) -> Result<Option<Quote>>;
     async fn save_quotes(&self, quotes: &[Quote]) -> Result<()>;
     async fn save_quote(&self, quote: &Quote) -> Result<Quote>;
     async fn delete_quote(&self, quote_id: &str) -> Result<()>;
@@ -1,21 +1,41 @@
+pub mod activity_importer;
+pub mod candles;
+pub mod currency;
 pub(crate) mod market_data_constants;
 pub(crate) mod market_data_errors;
 pub mod market_data_model;
 pub(crate) mod market_data_repository;
 pub(crate) mod market_data_service;
+pub mod market_data_sync_actor;
+pub(crate) mod quote_cache;
 pub mod market_data_traits;
+pub mod provider_config;
 pub mod providers;
+pub mod quote_query;
+pub(crate) mod quotes_coordinator;
+pub mod request_time;
+pub(crate) mod retry;
+pub mod staleness;
 
 // Re-export the public interface
+pub use activity_importer::{ActivityImporter, ActivityType, AlpacaActivityImporter, ImportedActivity, LedgerExporter};
+pub use candles::{resample_into_candles, CandleInterval, WeekBoundary};
+pub use currency::{Currency, Exchange};
 pub use market_data_constants::*;
 pub use market_data_model::{Quote, QuoteSummary, QuoteRequest, DataSource, MarketDataProviderInfo, MarketDataProviderSetting, QuoteImport, ImportValidationStatus};
 pub use market_data_repository::MarketDataRepository;
 pub use market_data_service::MarketDataService;
+pub use provider_config::{ProviderConfig, RateLimiter, RateLimiterRegistry};
 pub use market_data_traits::{MarketDataServiceTrait, MarketDataRepositoryTrait};
+pub use market_data_sync_actor::{MarketDataSyncActor, SyncJob, SyncJobOutcome};
+pub use quote_query::QuoteQuery;
+pub(crate) use quotes_coordinator::QuotesCoordinator;
+pub use request_time::RequestTime;
+pub use staleness::{is_outdated_quote, DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS};
 
 // Re-export provider types
 pub use providers::market_data_provider::{MarketDataProvider, AssetProfiler};
-pub use providers::provider_registry::ProviderRegistry;
+pub use providers::provider_registry::{ProviderRegistry, ProviderHealth};
 
 // Re-export error types for convenience
 pub use market_data_errors::MarketDataError;
@@ -0,0 +1,131 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::Instant;
+
+/// User-supplied configuration for a provider that talks to a paid/rate
+/// limited API (AlphaVantage, KuCoin, ...), as opposed to the keyless
+/// providers (Yahoo, VN_MARKET) whose endpoints are compiled in. Set through
+/// `MarketDataService::set_provider_config`, which keeps it in memory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderConfig {
+    /// Overrides the provider's compiled-in endpoint, for self-hosted or
+    /// region-specific API gateways.
+    pub base_url: Option<String>,
+    /// API key/token. Held in memory with the rest of this config (not
+    /// written to `SecretManager`), never logged or echoed back to the caller.
+    pub api_token: Option<String>,
+    /// Caps outbound requests per minute. `None` means unlimited.
+    pub requests_per_minute: Option<u32>,
+}
+
+/// A sliding-window rate limiter shared by a provider's HTTP calls.
+/// `acquire` blocks only when the window is already full, so bursts up to
+/// the configured limit pass through immediately.
+pub struct RateLimiter {
+    limit_per_minute: u32,
+    timestamps: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new(limit_per_minute: u32) -> Self {
+        Self {
+            limit_per_minute,
+            timestamps: Mutex::new(VecDeque::with_capacity(limit_per_minute as usize)),
+        }
+    }
+
+    /// Builds a limiter from an optional per-provider config, returning
+    /// `None` when no limit was configured so callers can skip the
+    /// bookkeeping entirely for unlimited providers.
+    pub fn from_config(config: &ProviderConfig) -> Option<Self> {
+        config.requests_per_minute.map(Self::new)
+    }
+
+    pub fn limit_per_minute(&self) -> u32 {
+        self.limit_per_minute
+    }
+
+    /// Waits until issuing another request would not exceed the configured
+    /// per-minute limit, then records this call's timestamp.
+    pub async fn acquire(&self) {
+        if self.limit_per_minute == 0 {
+            return;
+        }
+
+        let window = Duration::from_secs(60);
+        loop {
+            let wait = {
+                let mut timestamps = self.timestamps.lock().await;
+                let now = Instant::now();
+                while timestamps.front().is_some_and(|oldest| now.duration_since(*oldest) >= window) {
+                    timestamps.pop_front();
+                }
+
+                if timestamps.len() < self.limit_per_minute as usize {
+                    timestamps.push_back(now);
+                    None
+                } else {
+                    Some(window - now.duration_since(*timestamps.front().expect("window is full")))
+                }
+            };
+
+            match wait {
+                None => return,
+                Some(delay) => tokio::time::sleep(delay).await,
+            }
+        }
+    }
+}
+
+/// Per-provider [`RateLimiter`]s, built from each provider's configured
+/// `requests_per_minute` and rebuilt whenever that configuration changes.
+/// Shared between `MarketDataSyncActor` and `QuotesCoordinator` so a
+/// provider's request budget is enforced regardless of which path reaches
+/// it, instead of each keeping (and racing) its own bookkeeping.
+#[derive(Default)]
+pub struct RateLimiterRegistry {
+    limiters: Mutex<HashMap<String, Arc<RateLimiter>>>,
+}
+
+impl RateLimiterRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits until `provider_id` has budget for another request under the
+    /// limit currently configured for it in `provider_configs`. A provider
+    /// with no configured limit (or no entry at all) passes straight
+    /// through. Re-reads the configured limit on every call and rebuilds
+    /// the cached limiter whenever it no longer matches, so a limit changed
+    /// later via `set_provider_config` takes effect on the next request
+    /// instead of being stuck with whatever was configured when the
+    /// provider was first seen here.
+    pub async fn acquire(&self, provider_id: &str, provider_configs: &RwLock<HashMap<String, ProviderConfig>>) {
+        let configured_limit = provider_configs.read().await.get(provider_id).and_then(|c| c.requests_per_minute);
+
+        let limiter = {
+            let mut limiters = self.limiters.lock().await;
+            let cached_is_current = limiters
+                .get(provider_id)
+                .is_some_and(|limiter| Some(limiter.limit_per_minute()) == configured_limit);
+
+            if cached_is_current {
+                limiters.get(provider_id).expect("checked above").clone()
+            } else {
+                let Some(limit) = configured_limit else {
+                    limiters.remove(provider_id);
+                    return;
+                };
+                let limiter = Arc::new(RateLimiter::new(limit));
+                limiters.insert(provider_id.to_string(), limiter.clone());
+                limiter
+            }
+        };
+
+        limiter.acquire().await;
+    }
+}
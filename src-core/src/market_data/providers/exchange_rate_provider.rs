@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use crate::market_data::market_data_model::DataSource;
+use crate::market_data::providers::market_data_provider::MarketDataProvider;
+use crate::market_data::{MarketDataError, Quote as ModelQuote};
+
+const BASE_URL: &str = "https://api.frankfurter.app";
+
+/// A dedicated, keyless forex provider for `QuoteQuery::Forex` pairs.
+/// Unlike the equity/fund providers, it answers currency pairs exclusively
+/// (its `capabilities_for` entry in `ProviderRegistry` declares only
+/// `AssetDomain::Forex`, so it is never matched against a bare stock symbol).
+pub struct ExchangeRateProvider {
+    client: Client,
+}
+
+impl ExchangeRateProvider {
+    pub fn new() -> Self {
+        Self { client: Client::new() }
+    }
+
+    /// Splits a synthetic forex symbol like `USDEUR=X` (see
+    /// `QuoteQuery::symbol`) into its 3-letter `(base, quote)` currency
+    /// codes.
+    fn split_pair(symbol: &str) -> Result<(&str, &str), MarketDataError> {
+        let pair = symbol.trim_end_matches("=X");
+        if pair.len() != 6 {
+            return Err(MarketDataError::UnsupportedProvider(format!(
+                "'{}' is not a 6-letter currency pair",
+                symbol
+            )));
+        }
+        Ok(pair.split_at(3))
+    }
+}
+
+impl Default for ExchangeRateProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestRateResponse {
+    date: String,
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoricalRatesResponse {
+    rates: HashMap<String, HashMap<String, f64>>,
+}
+
+#[async_trait]
+impl MarketDataProvider for ExchangeRateProvider {
+    fn name(&self) -> &'static str {
+        "EXCHANGE_RATE"
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+
+    async fn get_latest_quote(
+        &self,
+        symbol: &str,
+        _fallback_currency: String,
+    ) -> Result<ModelQuote, MarketDataError> {
+        let (base, quote) = Self::split_pair(symbol)?;
+        let url = format!("{}/latest", BASE_URL);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("from", base), ("to", quote)])
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("ExchangeRate API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        let latest: LatestRateResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse response: {}", e)))?;
+
+        let rate = latest
+            .rates
+            .get(quote)
+            .copied()
+            .and_then(Decimal::from_f64_retain)
+            .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))?;
+
+        let date = NaiveDate::parse_from_str(&latest.date, "%Y-%m-%d")
+            .map_err(|e| MarketDataError::ProviderError(format!("Invalid date '{}': {}", latest.date, e)))?;
+        let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default());
+
+        Ok(ModelQuote {
+            id: format!("{}_{}", timestamp.format("%Y%m%d"), symbol),
+            symbol: symbol.to_string(),
+            timestamp,
+            open: rate,
+            high: rate,
+            low: rate,
+            close: rate,
+            adjclose: rate,
+            volume: Decimal::ZERO,
+            currency: quote.to_string(),
+            data_source: DataSource::ExchangeRate,
+            created_at: Utc::now(),
+        })
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        symbol: &str,
+        start: SystemTime,
+        end: SystemTime,
+        _fallback_currency: String,
+    ) -> Result<Vec<ModelQuote>, MarketDataError> {
+        let (base, quote) = Self::split_pair(symbol)?;
+
+        let start_date: DateTime<Utc> = start.into();
+        let end_date: DateTime<Utc> = end.into();
+        let url = format!(
+            "{}/{}..{}",
+            BASE_URL,
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("from", base), ("to", quote)])
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("ExchangeRate API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        let historical: HistoricalRatesResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse response: {}", e)))?;
+
+        let mut quotes: Vec<ModelQuote> = historical
+            .rates
+            .into_iter()
+            .filter_map(|(date_str, rates)| {
+                let date = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d").ok()?;
+                let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
+                let rate = Decimal::from_f64_retain(*rates.get(quote)?)?;
+
+                Some(ModelQuote {
+                    id: format!("{}_{}", timestamp.format("%Y%m%d"), symbol),
+                    symbol: symbol.to_string(),
+                    timestamp,
+                    open: rate,
+                    high: rate,
+                    low: rate,
+                    close: rate,
+                    adjclose: rate,
+                    volume: Decimal::ZERO,
+                    currency: quote.to_string(),
+                    data_source: DataSource::ExchangeRate,
+                    created_at: Utc::now(),
+                })
+            })
+            .collect();
+
+        if quotes.is_empty() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        quotes.sort_by_key(|q| q.timestamp);
+        Ok(quotes)
+    }
+
+    async fn get_historical_quotes_bulk(
+        &self,
+        symbols_with_currencies: &[(String, String, Option<String>)],
+        start: SystemTime,
+        end: SystemTime,
+    ) -> Result<(Vec<ModelQuote>, Vec<(String, String, Option<String>)>), MarketDataError> {
+        let mut all_quotes = Vec::new();
+        let mut failed_symbols = Vec::new();
+
+        for (symbol, currency, exchange) in symbols_with_currencies {
+            match self.get_historical_quotes(symbol, start, end, currency.clone()).await {
+                Ok(mut quotes) => all_quotes.append(&mut quotes),
+                Err(_) => failed_symbols.push((symbol.clone(), currency.clone(), exchange.clone())),
+            }
+        }
+
+        Ok((all_quotes, failed_symbols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_a_six_letter_pair_into_base_and_quote() {
+        assert_eq!(ExchangeRateProvider::split_pair("USDEUR=X").unwrap(), ("USD", "EUR"));
+    }
+
+    #[test]
+    fn splits_a_pair_without_the_x_suffix() {
+        assert_eq!(ExchangeRateProvider::split_pair("GBPJPY").unwrap(), ("GBP", "JPY"));
+    }
+
+    #[test]
+    fn rejects_a_symbol_that_is_not_six_letters() {
+        assert!(ExchangeRateProvider::split_pair("USD=X").is_err());
+        assert!(ExchangeRateProvider::split_pair("USDEURX=X").is_err());
+    }
+}
@@ -0,0 +1,513 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use sha2::Sha256;
+use std::time::SystemTime;
+
+use crate::market_data::market_data_model::DataSource;
+use crate::market_data::provider_config::{ProviderConfig, RateLimiter};
+use crate::market_data::providers::market_data_provider::MarketDataProvider;
+use crate::market_data::providers::models::AssetProfile;
+use crate::market_data::{AssetProfiler, MarketDataError, Quote as ModelQuote, QuoteSummary};
+
+const DEFAULT_BASE_URL: &str = "https://api.kucoin.com";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Crypto quotes and search via KuCoin's REST API. Public endpoints
+/// (ticker, candles, the symbol list) need no credentials; an API
+/// key/secret/passphrase only unlocks the private account endpoints (see
+/// [`Self::get_account_balances`]), so `get_latest_quote`/
+/// `get_historical_quotes`/`search_ticker` all work anonymously.
+pub struct KuCoinProvider {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    api_passphrase: Option<String>,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl KuCoinProvider {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            api_key: None,
+            api_secret: None,
+            api_passphrase: None,
+            rate_limiter: None,
+        }
+    }
+
+    pub fn with_credentials(mut self, api_key: String, api_secret: String, api_passphrase: String) -> Self {
+        self.api_key = Some(api_key);
+        self.api_secret = Some(api_secret);
+        self.api_passphrase = Some(api_passphrase);
+        self
+    }
+
+    /// Splits the single secret `ProviderRegistry` resolves through
+    /// `SecretManager::get_secret("KU_COIN")` into KuCoin's three-part
+    /// credential set. `SecretManager` only stores one secret string per
+    /// provider id, so the convention here is `"key|secret|passphrase"`
+    /// rather than a new per-provider multi-value store. Returns `None`
+    /// when the secret isn't in that shape, so the caller can fall back to
+    /// public-endpoint-only access instead of constructing with partial
+    /// credentials.
+    pub fn parse_credentials(secret: &str) -> Option<(String, String, String)> {
+        let mut parts = secret.splitn(3, '|');
+        let api_key = parts.next()?.to_string();
+        let api_secret = parts.next()?.to_string();
+        let api_passphrase = parts.next()?.to_string();
+        if api_key.is_empty() || api_secret.is_empty() || api_passphrase.is_empty() {
+            return None;
+        }
+        Some((api_key, api_secret, api_passphrase))
+    }
+
+    /// Applies a user-configured base URL override and requests-per-minute
+    /// limit. `config.api_token` isn't used here: KuCoin signs requests
+    /// with a key/secret/passphrase triple (see [`Self::with_credentials`]),
+    /// not a single bearer token.
+    pub fn with_config(mut self, config: &ProviderConfig) -> Self {
+        if let Some(base_url) = &config.base_url {
+            self.base_url = base_url.clone();
+        }
+        self.rate_limiter = RateLimiter::from_config(config);
+        self
+    }
+
+    /// Waits for rate-limiter clearance before issuing a request, a no-op
+    /// when no limit was configured.
+    async fn throttle(&self) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+    }
+
+    /// Builds KuCoin's `KC-API-*` signing headers for a private endpoint:
+    /// the request is signed as `timestamp + method + endpoint + body`,
+    /// HMAC-SHA256'd with the API secret and base64-encoded; the
+    /// passphrase is signed the same way. Returns `None` when no
+    /// credentials are configured.
+    fn sign_request(
+        &self,
+        method: &str,
+        endpoint: &str,
+        body: &str,
+    ) -> Result<Option<[(&'static str, String); 4]>, MarketDataError> {
+        let (Some(api_key), Some(api_secret), Some(passphrase)) =
+            (&self.api_key, &self.api_secret, &self.api_passphrase)
+        else {
+            return Ok(None);
+        };
+
+        let timestamp = Utc::now().timestamp_millis().to_string();
+        let prehash = format!("{}{}{}{}", timestamp, method, endpoint, body);
+        let signature = Self::sign(api_secret, &prehash)?;
+        let signed_passphrase = Self::sign(api_secret, passphrase)?;
+
+        Ok(Some([
+            ("KC-API-KEY", api_key.clone()),
+            ("KC-API-SIGN", signature),
+            ("KC-API-TIMESTAMP", timestamp),
+            ("KC-API-PASSPHRASE", signed_passphrase),
+        ]))
+    }
+
+    fn sign(secret: &str, message: &str) -> Result<String, MarketDataError> {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+            .map_err(|e| MarketDataError::ProviderError(format!("Invalid KuCoin API secret: {}", e)))?;
+        mac.update(message.as_bytes());
+        Ok(STANDARD.encode(mac.finalize().into_bytes()))
+    }
+
+    /// Splits a KuCoin trading pair like `BTC-USDT` into `(base, quote)`,
+    /// falling back to USDT when the symbol carries no `-quote` suffix.
+    fn split_pair(symbol: &str) -> (&str, &str) {
+        symbol.split_once('-').unwrap_or((symbol, "USDT"))
+    }
+
+    /// Fetches the authenticated account's balances via KuCoin's private
+    /// `/api/v1/accounts` endpoint, exercising the signed-request path.
+    /// Not wired into `MarketDataProvider`/`AssetProfiler` (neither trait
+    /// has an account-balance concept), but available for a future
+    /// holdings-sync feature to call directly.
+    pub(crate) async fn get_account_balances(&self) -> Result<Vec<(String, Decimal)>, MarketDataError> {
+        const ENDPOINT: &str = "/api/v1/accounts";
+
+        let headers = self.sign_request("GET", ENDPOINT, "")?.ok_or_else(|| {
+            MarketDataError::ProviderError("KuCoin credentials are not configured".to_string())
+        })?;
+
+        let mut request = self.client.get(format!("{}{}", self.base_url, ENDPOINT));
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        request = request.header("KC-API-KEY-VERSION", "2");
+
+        self.throttle().await;
+        let response = request
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("KuCoin API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(MarketDataError::ProviderError(format!("KuCoin API error: {}", error_body)));
+        }
+
+        let accounts: AccountsResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse KuCoin response: {}", e)))?;
+
+        Ok(accounts
+            .data
+            .into_iter()
+            .filter_map(|account| Some((account.currency, account.balance.parse::<Decimal>().ok()?)))
+            .collect())
+    }
+}
+
+impl Default for KuCoinProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerResponse {
+    data: TickerData,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickerData {
+    price: String,
+    time: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct KlinesResponse {
+    data: Vec<[String; 7]>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolsResponse {
+    data: Vec<SymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolInfo {
+    symbol: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountsResponse {
+    data: Vec<AccountBalance>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountBalance {
+    currency: String,
+    balance: String,
+}
+
+#[async_trait]
+impl MarketDataProvider for KuCoinProvider {
+    fn name(&self) -> &'static str {
+        "KU_COIN"
+    }
+
+    fn priority(&self) -> u8 {
+        6
+    }
+
+    async fn get_latest_quote(
+        &self,
+        symbol: &str,
+        fallback_currency: String,
+    ) -> Result<ModelQuote, MarketDataError> {
+        let (_, quote_currency) = Self::split_pair(symbol);
+        let url = format!("{}/api/v1/market/orderbook/level1", self.base_url);
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("symbol", symbol)])
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("KuCoin API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        let ticker: TickerResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse KuCoin response: {}", e)))?;
+
+        let price = ticker.data.price.parse::<Decimal>().map_err(|e| {
+            MarketDataError::ProviderError(format!("Invalid KuCoin price '{}': {}", ticker.data.price, e))
+        })?;
+        let timestamp = DateTime::<Utc>::from_timestamp_millis(ticker.data.time).unwrap_or_else(Utc::now);
+        let currency = if quote_currency.is_empty() {
+            fallback_currency
+        } else {
+            quote_currency.to_string()
+        };
+
+        Ok(ModelQuote {
+            id: format!("{}_{}", timestamp.format("%Y%m%d%H%M%S"), symbol),
+            symbol: symbol.to_string(),
+            timestamp,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            adjclose: price,
+            volume: Decimal::ZERO,
+            currency,
+            data_source: DataSource::KuCoin,
+            created_at: Utc::now(),
+        })
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        symbol: &str,
+        start: SystemTime,
+        end: SystemTime,
+        fallback_currency: String,
+    ) -> Result<Vec<ModelQuote>, MarketDataError> {
+        let (_, quote_currency) = Self::split_pair(symbol);
+        let currency = if quote_currency.is_empty() {
+            fallback_currency
+        } else {
+            quote_currency.to_string()
+        };
+
+        let start_secs = start.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+        let end_secs = end.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        let url = format!("{}/api/v1/market/candles", self.base_url);
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("type", "1day"),
+                ("symbol", symbol),
+                ("startAt", &start_secs.to_string()),
+                ("endAt", &end_secs.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("KuCoin API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        let klines: KlinesResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse KuCoin response: {}", e)))?;
+
+        // KuCoin candles are `[time, open, close, high, low, volume, turnover]`.
+        let quotes: Vec<ModelQuote> = klines
+            .data
+            .into_iter()
+            .filter_map(|[time, open, close, high, low, volume, _turnover]| {
+                let timestamp = DateTime::<Utc>::from_timestamp(time.parse::<i64>().ok()?, 0)?;
+                let close = close.parse::<Decimal>().ok()?;
+                Some(ModelQuote {
+                    id: format!("{}_{}", timestamp.format("%Y%m%d"), symbol),
+                    symbol: symbol.to_string(),
+                    timestamp,
+                    open: open.parse::<Decimal>().ok()?,
+                    high: high.parse::<Decimal>().ok()?,
+                    low: low.parse::<Decimal>().ok()?,
+                    close,
+                    adjclose: close,
+                    volume: volume.parse::<Decimal>().unwrap_or_default(),
+                    currency: currency.clone(),
+                    data_source: DataSource::KuCoin,
+                    created_at: Utc::now(),
+                })
+            })
+            .collect();
+
+        if quotes.is_empty() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        Ok(quotes)
+    }
+
+    async fn get_historical_quotes_bulk(
+        &self,
+        symbols_with_currencies: &[(String, String, Option<String>)],
+        start: SystemTime,
+        end: SystemTime,
+    ) -> Result<(Vec<ModelQuote>, Vec<(String, String, Option<String>)>), MarketDataError> {
+        let mut all_quotes = Vec::new();
+        let mut failed_symbols = Vec::new();
+
+        for (symbol, currency, exchange) in symbols_with_currencies {
+            match self.get_historical_quotes(symbol, start, end, currency.clone()).await {
+                Ok(mut quotes) => all_quotes.append(&mut quotes),
+                Err(_) => failed_symbols.push((symbol.clone(), currency.clone(), exchange.clone())),
+            }
+        }
+
+        Ok((all_quotes, failed_symbols))
+    }
+}
+
+#[async_trait]
+impl AssetProfiler for KuCoinProvider {
+    async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
+        let (base, quote) = Self::split_pair(symbol);
+
+        Ok(AssetProfile {
+            id: None,
+            isin: None,
+            symbol: symbol.to_string(),
+            symbol_mapping: None,
+            name: Some(format!("{}/{}", base, quote)),
+            asset_type: Some("CRYPTOCURRENCY".to_string()),
+            asset_class: Some("Cryptocurrency".to_string()),
+            asset_sub_class: Some("Cryptocurrency".to_string()),
+            currency: quote.to_string(),
+            data_source: "KU_COIN".to_string(),
+            notes: None,
+            countries: None,
+            categories: None,
+            classes: None,
+            attributes: None,
+            sectors: None,
+            url: None,
+        })
+    }
+
+    async fn search_ticker(&self, query: &str) -> Result<Vec<QuoteSummary>, MarketDataError> {
+        let url = format!("{}/api/v1/symbols", self.base_url);
+
+        self.throttle().await;
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("KuCoin API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Ok(Vec::new());
+        }
+
+        let symbols: SymbolsResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse KuCoin response: {}", e)))?;
+
+        let query_lower = query.to_lowercase();
+        let mut results: Vec<QuoteSummary> = symbols
+            .data
+            .into_iter()
+            .filter(|symbol_info| {
+                symbol_info.symbol.to_lowercase().contains(&query_lower)
+                    || symbol_info.name.to_lowercase().contains(&query_lower)
+            })
+            .map(|symbol_info| {
+                let symbol_lower = symbol_info.symbol.to_lowercase();
+                let score = if symbol_lower == query_lower {
+                    1.0
+                } else if symbol_lower.starts_with(&query_lower) {
+                    0.9
+                } else {
+                    0.6
+                };
+
+                QuoteSummary {
+                    symbol: symbol_info.symbol,
+                    short_name: symbol_info.name.clone(),
+                    long_name: symbol_info.name,
+                    exchange: "KUCOIN".to_string(),
+                    quote_type: "CRYPTOCURRENCY".to_string(),
+                    type_display: "Cryptocurrency".to_string(),
+                    index: "".to_string(),
+                    score,
+                }
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(10);
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_produces_a_stable_base64_hmac_for_a_known_secret() {
+        // The HMAC-SHA256 signature is deterministic for a given secret and
+        // message, and changes when either of them changes.
+        let signature = KuCoinProvider::sign("test-secret", "test-message").unwrap();
+        assert_eq!(signature, KuCoinProvider::sign("test-secret", "test-message").unwrap());
+        assert_ne!(signature, KuCoinProvider::sign("other-secret", "test-message").unwrap());
+        assert_ne!(signature, KuCoinProvider::sign("test-secret", "other-message").unwrap());
+    }
+
+    #[test]
+    fn sign_request_is_none_without_credentials() {
+        let provider = KuCoinProvider::new();
+        assert!(provider.sign_request("GET", "/api/v1/accounts", "").unwrap().is_none());
+    }
+
+    #[test]
+    fn sign_request_includes_all_four_kucoin_headers_once_credentialed() {
+        let provider = KuCoinProvider::new().with_credentials(
+            "key".to_string(),
+            "secret".to_string(),
+            "passphrase".to_string(),
+        );
+
+        let headers = provider.sign_request("GET", "/api/v1/accounts", "").unwrap().unwrap();
+        let names: Vec<&str> = headers.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["KC-API-KEY", "KC-API-SIGN", "KC-API-TIMESTAMP", "KC-API-PASSPHRASE"]);
+        assert_eq!(headers[0].1, "key");
+    }
+
+    #[test]
+    fn split_pair_falls_back_to_usdt_without_a_quote_suffix() {
+        assert_eq!(KuCoinProvider::split_pair("BTC-USDT"), ("BTC", "USDT"));
+        assert_eq!(KuCoinProvider::split_pair("BTC"), ("BTC", "USDT"));
+    }
+
+    #[test]
+    fn parse_credentials_splits_the_pipe_delimited_secret() {
+        assert_eq!(
+            KuCoinProvider::parse_credentials("key|secret|passphrase"),
+            Some(("key".to_string(), "secret".to_string(), "passphrase".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_credentials_rejects_a_malformed_or_incomplete_secret() {
+        assert_eq!(KuCoinProvider::parse_credentials("just-a-key"), None);
+        assert_eq!(KuCoinProvider::parse_credentials("key|secret|"), None);
+    }
+}
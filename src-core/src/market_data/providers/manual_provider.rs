@@ -6,12 +6,28 @@ use crate::market_data::market_data_model::Quote;
 use std::time::SystemTime;
 
 use super::models::AssetProfile;
+
+/// Prefix marking a synthetic cash-balance symbol, e.g. `$CASH-USD`.
+const CASH_SYMBOL_PREFIX: &str = "$CASH-";
+
 pub struct ManualProvider;
 
 impl ManualProvider {
     pub fn new() -> Result<Self, MarketDataError> {
         Ok(ManualProvider)
     }
+
+    /// Extracts the currency code from a `$CASH-XXX` symbol, rather than
+    /// blindly slicing bytes after the prefix, so a malformed or truncated
+    /// cash symbol doesn't silently resolve to a bogus currency.
+    fn parse_cash_currency(symbol: &str) -> Option<&str> {
+        let currency = symbol.strip_prefix(CASH_SYMBOL_PREFIX)?;
+        if currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase()) {
+            Some(currency)
+        } else {
+            None
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -47,7 +63,7 @@ impl MarketDataProvider for ManualProvider {
 #[async_trait::async_trait]
 impl AssetProfiler for ManualProvider {
     async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
-        if symbol.starts_with("$CASH-") {
+        if let Some(currency) = Self::parse_cash_currency(symbol) {
             Ok(AssetProfile {
                 id: Some(symbol.to_string()),
                 isin: None,
@@ -57,9 +73,14 @@ impl AssetProfiler for ManualProvider {
                 asset_sub_class: Some("CASH".to_string()),
                 symbol: symbol.to_string(),
                 data_source: DataSource::Manual.as_str().to_string(),
-                currency: symbol[6..].to_string(),
+                currency: currency.to_string(),
                 ..Default::default()
             })
+        } else if symbol.starts_with(CASH_SYMBOL_PREFIX) {
+            Err(MarketDataError::ProviderError(format!(
+                "'{}' looks like a cash symbol but does not carry a valid 3-letter currency code",
+                symbol
+            )))
         } else {
             Ok(AssetProfile {
                 id: Some(symbol.to_string()),
@@ -1,11 +1,16 @@
 use crate::market_data::market_data_constants::{
     DATA_SOURCE_MANUAL, DATA_SOURCE_MARKET_DATA_APP, DATA_SOURCE_YAHOO,
-    DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_VN_MARKET
+    DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_EXCHANGE_RATE, DATA_SOURCE_KU_COIN,
+    DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_TINKOFF, DATA_SOURCE_VN_MARKET,
 };
 use crate::market_data::market_data_errors::MarketDataError;
 use crate::market_data::market_data_model::{
     MarketDataProviderSetting, Quote as ModelQuote, QuoteSummary,
 };
+use crate::market_data::provider_config::ProviderConfig;
+use crate::market_data::quote_query::QuoteQuery;
+use crate::market_data::providers::exchange_rate_provider::ExchangeRateProvider;
+use crate::market_data::providers::kucoin_provider::KuCoinProvider;
 use crate::market_data::providers::manual_provider::ManualProvider;
 use crate::market_data::providers::market_data_provider::{AssetProfiler, MarketDataProvider};
 use crate::market_data::providers::marketdata_app_provider::MarketDataAppProvider;
@@ -14,10 +19,11 @@ use crate::market_data::providers::alpha_vantage_provider::AlphaVantageProvider;
 use crate::market_data::providers::vn_market_provider::{VnMarketProvider};
 use crate::market_data::providers::yahoo_provider::YahooProvider;
 use crate::secrets::SecretManager;
+use chrono::{DateTime, Utc};
 use log::{debug, info, warn};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::SystemTime;
+use std::time::{Instant, SystemTime};
 
 pub struct ProviderRegistry {
     data_providers: HashMap<String, Arc<dyn MarketDataProvider + Send + Sync>>,
@@ -50,8 +56,9 @@ impl ProviderRegistry {
 
             let provider_id_str = &setting.id;
 
-            let api_key = if provider_id_str != DATA_SOURCE_YAHOO 
-                && provider_id_str != DATA_SOURCE_VN_MARKET {
+            let api_key = if provider_id_str != DATA_SOURCE_YAHOO
+                && provider_id_str != DATA_SOURCE_VN_MARKET
+                && provider_id_str != DATA_SOURCE_EXCHANGE_RATE {
                 match SecretManager::get_secret(provider_id_str) {
                     Ok(key_opt) => key_opt,
                     Err(e) => {
@@ -144,6 +151,55 @@ impl ProviderRegistry {
                         Some(p as Arc<dyn AssetProfiler + Send + Sync>),
                     )
                 }
+                DATA_SOURCE_EXCHANGE_RATE => {
+                    let p = Arc::new(ExchangeRateProvider::new());
+                    (
+                        Some(p as Arc<dyn MarketDataProvider + Send + Sync>),
+                        None,
+                    )
+                }
+                DATA_SOURCE_KU_COIN => {
+                    // Unlike AlphaVantage/MetalPriceApi/MarketDataApp,
+                    // KuCoin's public endpoints (used by
+                    // `get_latest_quote`/`get_historical_quotes`/
+                    // `search_ticker`) work without credentials, so it's
+                    // still registered when no secret resolves — only the
+                    // private account-balance endpoint needs the key/
+                    // secret/passphrase triple.
+                    let mut provider = KuCoinProvider::new();
+                    if let Some(secret) = api_key.filter(|s| !s.is_empty()) {
+                        match KuCoinProvider::parse_credentials(&secret) {
+                            Some((key, api_secret, passphrase)) => {
+                                provider = provider.with_credentials(key, api_secret, passphrase);
+                            }
+                            None => warn!(
+                                "KuCoin provider '{}' (ID: {}) has a credential secret that isn't 'key|secret|passphrase'; registering with public-endpoint access only.",
+                                setting.name, setting.id
+                            ),
+                        }
+                    }
+                    let p = Arc::new(provider);
+                    (
+                        Some(p.clone() as Arc<dyn MarketDataProvider + Send + Sync>),
+                        Some(p as Arc<dyn AssetProfiler + Send + Sync>),
+                    )
+                }
+                DATA_SOURCE_TINKOFF => {
+                    // `tinkoff_grpc` is a hand-written stand-in for
+                    // `prost`/`tonic-build`-generated code (see its module
+                    // doc): `find_instrument`/`get_last_prices` always
+                    // return `Status::unimplemented`, so every quote call
+                    // this provider could make is guaranteed to fail.
+                    // Registering it anyway would report it as a live,
+                    // selectable data source with no way for the UI/user
+                    // to tell it apart from a working one. Skip it until a
+                    // real compiled gRPC client backs it.
+                    warn!(
+                        "Tinkoff provider '{}' (ID: {}) has no working gRPC client yet (tinkoff_grpc is an unimplemented stub); skipping registration.",
+                        setting.name, setting.id
+                    );
+                    (None, None)
+                }
                 _ => {
                     warn!("Unknown provider ID: {}", provider_id_str);
                     (None, None)
@@ -190,18 +246,17 @@ impl ProviderRegistry {
         })
     }
 
+    /// Picks the highest-priority provider whose declared capabilities
+    /// match `symbol` with the best confidence, instead of the previous
+    /// hardcoded "`.VN` goes to VN_MARKET, everything else goes elsewhere"
+    /// rule. Adding a new regional provider only requires an entry in
+    /// `capabilities_for`, not a change to this routing logic.
     pub async fn get_provider_for_symbol(&self, symbol: &str) -> Option<&str> {
-        // Try each provider in order of priority
-        for provider_id in &self.ordered_data_provider_ids {
-            if let Some(provider) = self.data_providers.get(provider_id) {
-                // For now, we'll use a simple heuristic
-                // In a real implementation, you might want to check if the provider
-                // actually supports the symbol
-                if symbol.ends_with(".VN") || symbol.ends_with(".vn") {
-                    if provider_id == DATA_SOURCE_VN_MARKET {
-                        return Some(provider_id);
-                    }
-                } else if provider_id != DATA_SOURCE_VN_MARKET {
+        for confidence in [MatchConfidence::Exact, MatchConfidence::Low] {
+            for provider_id in &self.ordered_data_provider_ids {
+                if self.data_providers.contains_key(provider_id)
+                    && capabilities_for(provider_id).supports(symbol) == confidence
+                {
                     return Some(provider_id);
                 }
             }
@@ -209,6 +264,172 @@ impl ProviderRegistry {
         None
     }
 
+    /// Returns the registered provider ids in priority order (highest
+    /// priority first), for callers that need to walk every provider
+    /// rather than resolve a single one for a symbol.
+    pub fn ordered_provider_ids(&self) -> Vec<String> {
+        self.ordered_data_provider_ids.clone()
+    }
+
+    /// Returns every registered, enabled provider able to quote `symbol`,
+    /// in priority order, instead of just the single best match returned
+    /// by `get_provider_for_symbol`. Callers can fail over to the next
+    /// candidate when the first one errors or reports the symbol as
+    /// unavailable, rather than failing the whole request.
+    pub fn get_provider_candidates_for_symbol(&self, symbol: &str) -> Vec<String> {
+        let mut candidates = Vec::new();
+        for confidence in [MatchConfidence::Exact, MatchConfidence::Low] {
+            for provider_id in &self.ordered_data_provider_ids {
+                if self.data_providers.contains_key(provider_id)
+                    && capabilities_for(provider_id).supports(symbol) == confidence
+                    && !candidates.contains(provider_id)
+                {
+                    candidates.push(provider_id.clone());
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Resolves the provider for a typed query through the same
+    /// capability/confidence routing `get_provider_for_symbol` uses for
+    /// equities, instead of a separate hardcoded forex provider list —
+    /// `QuoteQuery::Forex`'s synthetic `EURUSD=X`-style symbol (see
+    /// `QuoteQuery::symbol`) is itself forex-shaped, so `capabilities_for`
+    /// routes it to whichever registered provider declares
+    /// `AssetDomain::Forex` (`ExchangeRateProvider`, then `MetalPriceApi`
+    /// as a fallback, by priority order).
+    pub async fn get_provider_for_query(&self, query: &QuoteQuery) -> Option<&str> {
+        match query {
+            QuoteQuery::Forex(_, _) => self.get_provider_for_symbol(&query.symbol()).await,
+            QuoteQuery::Stock(symbol, _exchanges) => self.get_provider_for_symbol(symbol).await,
+        }
+    }
+
+    /// Runs `provider_id`'s contract (a canary symbol lookup plus the
+    /// assertions in [`ProviderContract::verify`]) and records the
+    /// outcome, rather than assuming a provider works until a user-facing
+    /// query fails.
+    pub async fn verify_provider(&self, provider_id: &str) -> ProviderHealth {
+        let Some(provider) = self.data_providers.get(provider_id) else {
+            return ProviderHealth {
+                provider_id: provider_id.to_string(),
+                healthy: false,
+                latency_ms: 0,
+                last_success: None,
+                reason: Some("Provider is not registered".to_string()),
+            };
+        };
+
+        // Manual entries are user-typed, not fetched: `ManualProvider`
+        // always returns `UnsupportedProvider` from `get_historical_quotes`
+        // by design, so running the usual canary request against it would
+        // report the provider unhealthy even when every real provider is
+        // fine. Exempt it rather than have `ReverifyProviders` fail the
+        // whole sync over an expected no-op.
+        if provider_id == DATA_SOURCE_MANUAL {
+            return ProviderHealth {
+                provider_id: provider_id.to_string(),
+                healthy: true,
+                latency_ms: 0,
+                last_success: Some(Utc::now()),
+                reason: None,
+            };
+        }
+
+        let contract = ProviderContract::for_provider(provider_id);
+        let started = Instant::now();
+
+        let end = SystemTime::now();
+        let start = end - std::time::Duration::from_secs(7 * 24 * 60 * 60);
+        let result = provider
+            .get_historical_quotes(&contract.canary_symbol, start, end, contract.expected_currency.clone())
+            .await;
+
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(quotes) => match contract.verify(&quotes) {
+                Ok(()) => ProviderHealth {
+                    provider_id: provider_id.to_string(),
+                    healthy: true,
+                    latency_ms,
+                    last_success: Some(Utc::now()),
+                    reason: None,
+                },
+                Err(reason) => ProviderHealth {
+                    provider_id: provider_id.to_string(),
+                    healthy: false,
+                    latency_ms,
+                    last_success: None,
+                    reason: Some(reason),
+                },
+            },
+            Err(e) => ProviderHealth {
+                provider_id: provider_id.to_string(),
+                healthy: false,
+                latency_ms,
+                last_success: None,
+                reason: Some(e.to_string()),
+            },
+        }
+    }
+
+    /// Verifies every registered provider's contract, for the settings UI
+    /// to show which providers are actually reachable/valid.
+    pub async fn verify_all_providers(&self) -> Vec<ProviderHealth> {
+        let mut results = Vec::with_capacity(self.ordered_data_provider_ids.len());
+        for provider_id in &self.ordered_data_provider_ids {
+            results.push(self.verify_provider(provider_id).await);
+        }
+        results
+    }
+
+    /// Rebuilds a configurable provider's live instance from a freshly
+    /// saved [`ProviderConfig`] — the "rebuild the relevant provider with
+    /// `.with_config(...)`" half of applying
+    /// `MarketDataService::set_provider_config`, instead of leaving a saved
+    /// config read back only for the settings UI's `has_credentials` flag.
+    /// Resolved credentials are re-read from `SecretManager` rather than
+    /// carried over from the original construction, so a credential
+    /// rotation takes effect on the next config save too. A no-op for
+    /// provider ids that aren't currently registered or don't support
+    /// runtime reconfiguration.
+    pub async fn apply_provider_config(&mut self, provider_id: &str, config: &ProviderConfig) {
+        if !self.data_providers.contains_key(provider_id) {
+            return;
+        }
+
+        match provider_id {
+            DATA_SOURCE_KU_COIN => {
+                let mut provider = KuCoinProvider::new();
+                if let Some((key, secret, passphrase)) = SecretManager::get_secret(DATA_SOURCE_KU_COIN)
+                    .ok()
+                    .flatten()
+                    .and_then(|secret| KuCoinProvider::parse_credentials(&secret))
+                {
+                    provider = provider.with_credentials(key, secret, passphrase);
+                }
+                // KuCoin is registered as both a MarketDataProvider and an
+                // AssetProfiler (see `new()`), so both maps need the same
+                // rebuilt instance or asset-profile lookups would keep
+                // serving the stale pre-config provider.
+                let p = Arc::new(provider.with_config(config));
+                self.data_providers
+                    .insert(provider_id.to_string(), p.clone() as Arc<dyn MarketDataProvider + Send + Sync>);
+                self.asset_profilers
+                    .insert(provider_id.to_string(), p as Arc<dyn AssetProfiler + Send + Sync>);
+            }
+            // DATA_SOURCE_TINKOFF isn't handled here: `new()` never
+            // registers it (its gRPC client is a permanently-unimplemented
+            // stub, see the registration arm), so it can never reach the
+            // `contains_key` check above.
+            _ => return,
+        };
+
+        info!("Applied saved config to provider '{}'", provider_id);
+    }
+
     pub async fn get_provider(&self, provider_id: &str) -> Option<Arc<dyn MarketDataProvider + Send + Sync>> {
         self.data_providers.get(provider_id).cloned()
     }
@@ -224,35 +445,25 @@ impl ProviderRegistry {
             .collect()
     }
 
-    fn contains_vn_indicator(query: &str) -> bool {
-        query.to_uppercase().contains("VN")
-    }
-
     pub async fn search_ticker(&self, query: &str) -> Result<Vec<QuoteSummary>, MarketDataError> {
         let mut all_results = Vec::new();
         let mut errors = Vec::new();
 
-        // Determine profiler search order based on VN indicator in query
-        let search_order = if Self::contains_vn_indicator(query) {
-            // If query contains "VN", prioritize VN_MARKET
-            let mut reordered = Vec::new();
-            if self.ordered_profiler_ids.contains(&DATA_SOURCE_VN_MARKET.to_string()) {
-                reordered.push(DATA_SOURCE_VN_MARKET.to_string());
-            }
-            for profiler_id in &self.ordered_profiler_ids {
-                if profiler_id != DATA_SOURCE_VN_MARKET {
-                    reordered.push(profiler_id.clone());
-                }
-            }
-            reordered
-        } else {
-            // Use default priority order
-            self.ordered_profiler_ids.clone()
-        };
+        // Order profilers by how confidently their declared capabilities
+        // match the query, instead of a special-cased VN-indicator
+        // reorder. A new regional provider just needs an entry in
+        // `capabilities_for`.
+        let mut search_order: Vec<String> = self.ordered_profiler_ids.clone();
+        search_order.sort_by_key(|id| std::cmp::Reverse(capabilities_for(id).matches_search_query(query)));
 
         // Try each profiler in determined order
         for profiler_id in &search_order {
             if let Some(profiler) = self.asset_profilers.get(profiler_id) {
+                let confidence = capabilities_for(profiler_id).matches_search_query(query);
+                if confidence == MatchConfidence::None {
+                    continue;
+                }
+
                 match profiler.search_ticker(query).await {
                     Ok(mut results) => {
                         debug!(
@@ -261,10 +472,11 @@ impl ProviderRegistry {
                             profiler_id
                         );
                         all_results.append(&mut results);
-                        
-                        // Only break if we got results OR if this is a VN-prioritized search
-                        // and we're at VN_MARKET (even with 0 results, respect VN priority)
-                        if !results.is_empty() || (Self::contains_vn_indicator(query) && profiler_id == DATA_SOURCE_VN_MARKET) {
+
+                        // Only break if we got results OR the profiler was an
+                        // exact capability match (even with 0 results, respect
+                        // its specialization over a generic fallback).
+                        if !results.is_empty() || confidence == MatchConfidence::Exact {
                             break;
                         }
                     }
@@ -295,31 +507,243 @@ impl ProviderRegistry {
     }
 }
 
+/// How confidently a provider claims it can answer a given symbol or
+/// search query. Routing picks the highest-priority provider with the
+/// best confidence instead of falling through an implicit "everything
+/// else" rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchConfidence {
+    None,
+    Low,
+    Exact,
+}
+
+/// Coarse asset domain a provider serves. Routing doesn't have a real
+/// exchange directory to match against (none of these providers expose
+/// one), so a provider declares the domain(s) its symbols fall in — VN
+/// equities, crypto pairs, forex pairs, metals — and a bare `Generic`
+/// fallback for a global-equity source with no narrower specialty.
+///
+/// This, not a per-provider exchange list, is the "real capability set"
+/// each provider should declare: onboarding a new regional or asset-class
+/// provider means adding one entry to `capabilities_for`, not editing
+/// `supports`/`matches_search_query` or the routing methods that call
+/// them.
+///
+/// `supports`/`matches_search_query` are methods of the registry-local
+/// `ProviderCapabilities` rather than of the `MarketDataProvider` trait: that
+/// trait is defined in
+/// `providers/market_data_provider.rs`, which this change doesn't touch,
+/// so every provider's capability declaration is centralized in this one
+/// match instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssetDomain {
+    Vn,
+    Crypto,
+    Forex,
+    Metals,
+    Generic,
+}
+
+const VN_EXCHANGES: &[&str] = &["HOSE", "HNX", "UPCOM"];
+
+/// A provider's declared domain(s). Most providers serve exactly one;
+/// `MetalPriceApi` also quotes currency pairs, so it declares both.
+struct ProviderCapabilities {
+    domains: &'static [AssetDomain],
+}
+
+impl ProviderCapabilities {
+    /// Classifies a bare symbol by its shape — the same conventions the
+    /// providers themselves already rely on: VN's `.VN` suffix
+    /// (`VnMarketProvider`), KuCoin's `BASE-QUOTE` dash pairing
+    /// (`KuCoinProvider::split_pair`), and Yahoo's `=X` forex suffix
+    /// (`QuoteQuery::symbol`, `ExchangeRateProvider::split_pair`).
+    /// `None` means the symbol looks like a bare equity ticker, which only
+    /// a `Generic` provider should claim, and only at `Low` confidence.
+    fn classify(symbol: &str) -> Option<AssetDomain> {
+        if symbol.ends_with(".VN") || symbol.ends_with(".vn") {
+            Some(AssetDomain::Vn)
+        } else if symbol.ends_with("=X") || symbol.ends_with("=x") {
+            Some(AssetDomain::Forex)
+        } else if symbol.contains('-') {
+            Some(AssetDomain::Crypto)
+        } else {
+            None
+        }
+    }
+
+    fn supports(&self, symbol: &str) -> MatchConfidence {
+        match Self::classify(symbol) {
+            Some(domain) if self.domains.contains(&domain) => MatchConfidence::Exact,
+            Some(_) => MatchConfidence::None,
+            None if self.domains.contains(&AssetDomain::Generic) => MatchConfidence::Low,
+            None => MatchConfidence::None,
+        }
+    }
+
+    fn matches_search_query(&self, query: &str) -> MatchConfidence {
+        let upper = query.to_uppercase();
+        let domain = if upper.contains("VN") || VN_EXCHANGES.iter().any(|e| upper.contains(e)) {
+            Some(AssetDomain::Vn)
+        } else if upper.ends_with("=X") {
+            Some(AssetDomain::Forex)
+        } else if upper.contains('-') {
+            Some(AssetDomain::Crypto)
+        } else {
+            None
+        };
+
+        match domain {
+            Some(domain) if self.domains.contains(&domain) => MatchConfidence::Exact,
+            Some(_) => MatchConfidence::None,
+            None if self.domains.contains(&AssetDomain::Generic) => MatchConfidence::Low,
+            None => MatchConfidence::None,
+        }
+    }
+}
+
+/// Returns the declared capabilities for a registered provider id. New
+/// regional or asset-class providers are onboarded by adding an entry
+/// here, not by editing the registry's routing logic.
+fn capabilities_for(provider_id: &str) -> ProviderCapabilities {
+    match provider_id {
+        DATA_SOURCE_VN_MARKET => ProviderCapabilities { domains: &[AssetDomain::Vn] },
+        DATA_SOURCE_MANUAL => ProviderCapabilities { domains: &[AssetDomain::Generic] },
+        DATA_SOURCE_METAL_PRICE_API => {
+            ProviderCapabilities { domains: &[AssetDomain::Metals, AssetDomain::Forex] }
+        }
+        DATA_SOURCE_EXCHANGE_RATE => ProviderCapabilities { domains: &[AssetDomain::Forex] },
+        DATA_SOURCE_KU_COIN => ProviderCapabilities { domains: &[AssetDomain::Crypto] },
+        // Tinkoff (Russian equities via MOEX) and the remaining providers
+        // (Yahoo, AlphaVantage, MarketData.app) have no narrower domain
+        // modeled here, so — like Manual — they're generic global-equity
+        // fallbacks: `Low` confidence for an unrecognized symbol shape,
+        // never a blanket `Exact` over every symbol a sharper provider
+        // hasn't already claimed.
+        _ => ProviderCapabilities { domains: &[AssetDomain::Generic] },
+    }
+}
+
+/// A canary request a provider must satisfy to be considered healthy,
+/// mirroring contract-verification tooling like `pact_verifier`: a known
+/// symbol per capability plus assertions on the shape of the response.
+struct ProviderContract {
+    canary_symbol: String,
+    expected_currency: String,
+}
+
+impl ProviderContract {
+    /// Picks a canary symbol representative of what the provider claims
+    /// to serve (a VN-listed ticker for the `Vn` domain, a `BASE-QUOTE`
+    /// pair for `Crypto`, an `=X` currency pair for `Forex`, or a generic
+    /// blue-chip ticker otherwise).
+    fn for_provider(provider_id: &str) -> Self {
+        let domains = capabilities_for(provider_id).domains;
+        let canary_symbol = if domains.contains(&AssetDomain::Vn) {
+            "VNM.VN".to_string()
+        } else if domains.contains(&AssetDomain::Crypto) {
+            "BTC-USDT".to_string()
+        } else if domains.contains(&AssetDomain::Forex) {
+            "EURUSD=X".to_string()
+        } else {
+            "AAPL".to_string()
+        };
+
+        Self {
+            canary_symbol,
+            expected_currency: "USD".to_string(),
+        }
+    }
+
+    /// Asserts the contract against a canary response: non-empty,
+    /// monotonically increasing quote dates, and every quote carrying a
+    /// populated currency.
+    fn verify(&self, quotes: &[ModelQuote]) -> Result<(), String> {
+        if quotes.is_empty() {
+            return Err("Canary request returned no quotes".to_string());
+        }
+
+        if quotes.windows(2).any(|pair| pair[1].timestamp < pair[0].timestamp) {
+            return Err("Quote dates were not monotonically increasing".to_string());
+        }
+
+        if quotes.iter().any(|q| q.currency.trim().is_empty()) {
+            return Err("One or more quotes were missing a currency".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+/// Outcome of running a provider's [`ProviderContract`]: whether it
+/// passed, how long it took, and when it last succeeded.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProviderHealth {
+    pub provider_id: String,
+    pub healthy: bool,
+    pub latency_ms: u64,
+    pub last_success: Option<DateTime<Utc>>,
+    pub reason: Option<String>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_contains_vn_indicator() {
-        // Queries with VN indicator
-        assert!(ProviderRegistry::contains_vn_indicator("VN"));
-        assert!(ProviderRegistry::contains_vn_indicator("vn"));
-        assert!(ProviderRegistry::contains_vn_indicator("MBB.VN"));
-        assert!(ProviderRegistry::contains_vn_indicator("FPT.vn"));
-        assert!(ProviderRegistry::contains_vn_indicator("VN30"));
-        assert!(ProviderRegistry::contains_vn_indicator("VNINDEX"));
-        assert!(ProviderRegistry::contains_vn_indicator("vn_gold"));
-        assert!(ProviderRegistry::contains_vn_indicator("VN_OIL"));
-        assert!(ProviderRegistry::contains_vn_indicator("HNX"));
-        assert!(ProviderRegistry::contains_vn_indicator("UPCOM"));
-
-        // Queries without VN indicator
-        assert!(!ProviderRegistry::contains_vn_indicator("FPT"));
-        assert!(!ProviderRegistry::contains_vn_indicator("GOLD"));
-        assert!(!ProviderRegistry::contains_vn_indicator("SILVER"));
-        assert!(!ProviderRegistry::contains_vn_indicator("AAPL"));
-        assert!(!ProviderRegistry::contains_vn_indicator("MSFT"));
-        assert!(!ProviderRegistry::contains_vn_indicator(""));
-        assert!(!ProviderRegistry::contains_vn_indicator("XAU"));
+    fn test_vn_market_supports_vn_suffixed_symbols_only() {
+        let caps = capabilities_for(DATA_SOURCE_VN_MARKET);
+        assert_eq!(caps.supports("MBB.VN"), MatchConfidence::Exact);
+        assert_eq!(caps.supports("FPT.vn"), MatchConfidence::Exact);
+        assert_eq!(caps.supports("AAPL"), MatchConfidence::None);
+    }
+
+    #[test]
+    fn test_generic_provider_is_a_fallback_for_non_vn_symbols() {
+        let caps = capabilities_for("YAHOO");
+        assert_eq!(caps.supports("AAPL"), MatchConfidence::Low);
+        assert_eq!(caps.supports("MBB.VN"), MatchConfidence::None);
+    }
+
+    #[test]
+    fn test_search_query_capability_matches_mirror_symbol_routing() {
+        let vn_caps = capabilities_for(DATA_SOURCE_VN_MARKET);
+        assert_eq!(vn_caps.matches_search_query("VN30"), MatchConfidence::Exact);
+        assert_eq!(vn_caps.matches_search_query("HNX"), MatchConfidence::Exact);
+        assert_eq!(vn_caps.matches_search_query("AAPL"), MatchConfidence::None);
+
+        let generic_caps = capabilities_for("YAHOO");
+        assert_eq!(generic_caps.matches_search_query("AAPL"), MatchConfidence::Low);
+        assert_eq!(generic_caps.matches_search_query("VN30"), MatchConfidence::None);
+    }
+
+    #[test]
+    fn test_generic_providers_do_not_claim_crypto_or_forex_symbols() {
+        for provider_id in [DATA_SOURCE_TINKOFF, DATA_SOURCE_YAHOO, DATA_SOURCE_ALPHA_VANTAGE] {
+            let caps = capabilities_for(provider_id);
+            assert_eq!(caps.supports("BTC-USDT"), MatchConfidence::None);
+            assert_eq!(caps.supports("EURUSD=X"), MatchConfidence::None);
+            assert_eq!(caps.supports("AAPL"), MatchConfidence::Low);
+        }
+    }
+
+    #[test]
+    fn test_kucoin_supports_only_crypto_pairs() {
+        let caps = capabilities_for(DATA_SOURCE_KU_COIN);
+        assert_eq!(caps.supports("BTC-USDT"), MatchConfidence::Exact);
+        assert_eq!(caps.supports("AAPL"), MatchConfidence::None);
+        assert_eq!(caps.supports("EURUSD=X"), MatchConfidence::None);
+    }
+
+    #[test]
+    fn test_exchange_rate_and_metal_price_api_support_forex_pairs() {
+        let exchange_rate_caps = capabilities_for(DATA_SOURCE_EXCHANGE_RATE);
+        assert_eq!(exchange_rate_caps.supports("EURUSD=X"), MatchConfidence::Exact);
+        assert_eq!(exchange_rate_caps.supports("AAPL"), MatchConfidence::None);
+
+        let metal_caps = capabilities_for(DATA_SOURCE_METAL_PRICE_API);
+        assert_eq!(metal_caps.supports("EURUSD=X"), MatchConfidence::Exact);
+        assert_eq!(metal_caps.supports("BTC-USDT"), MatchConfidence::None);
     }
 }
@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+use tonic::transport::{Channel, Endpoint};
+use tonic::Request;
+
+use crate::market_data::market_data_model::DataSource;
+use crate::market_data::provider_config::ProviderConfig;
+use crate::market_data::providers::market_data_provider::MarketDataProvider;
+use crate::market_data::{MarketDataError, Quote as ModelQuote};
+
+use self::tinkoff_grpc::market_data_service_client::MarketDataServiceClient;
+use self::tinkoff_grpc::{GetLastPricesRequest, InstrumentRequest};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Tinkoff Invest's production gRPC gateway, used when no config override
+/// (or no real deployment endpoint) is available.
+pub const DEFAULT_ENDPOINT_URL: &str = "https://invest-public-api.tinkoff.ru:443";
+
+/// Tinkoff-style `GetLastPrices` quotes via a single gRPC call for every
+/// requested symbol, instead of one request per symbol. Resolves each
+/// symbol to the exchange's own FIGI/instrument-uid once and caches the
+/// mapping, since `GetLastPrices` is keyed by instrument id, not ticker.
+///
+/// Not currently registered by `ProviderRegistry::new()`: every method
+/// here bottoms out in `tinkoff_grpc`, a hand-written stub that always
+/// returns `Status::unimplemented` (no compiled `.proto` client exists in
+/// this tree), so there is no input for which this provider can succeed.
+/// It stays in the tree as the shape a real implementation would fill in.
+pub struct TinkoffProvider {
+    endpoint_url: String,
+    figi_cache: RwLock<HashMap<String, String>>,
+}
+
+impl TinkoffProvider {
+    pub fn new(endpoint_url: String) -> Self {
+        Self {
+            endpoint_url,
+            figi_cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Applies a user-configured endpoint override. `config.api_token` and
+    /// `config.requests_per_minute` go unused for now: the stubbed gRPC
+    /// client in this module doesn't yet send request-level auth or have a
+    /// rate limiter wired in (see the module doc on `tinkoff_grpc`).
+    pub fn with_config(mut self, config: &ProviderConfig) -> Self {
+        if let Some(base_url) = &config.base_url {
+            self.endpoint_url = base_url.clone();
+        }
+        self
+    }
+
+    async fn connect(&self) -> Result<MarketDataServiceClient<Channel>, MarketDataError> {
+        let endpoint = Endpoint::from_shared(self.endpoint_url.clone())
+            .map_err(|e| MarketDataError::ProviderError(format!("Invalid Tinkoff endpoint: {}", e)))?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT);
+
+        endpoint
+            .connect()
+            .await
+            .map(MarketDataServiceClient::new)
+            .map_err(|e| MarketDataError::ProviderError(format!("Tinkoff gRPC connect error: {}", e)))
+    }
+
+    /// Resolves `symbol` to its instrument id (FIGI/uid), caching the
+    /// result so a repeated lookup for the same symbol doesn't issue
+    /// another RPC.
+    async fn resolve_instrument_id(
+        &self,
+        client: &mut MarketDataServiceClient<Channel>,
+        symbol: &str,
+    ) -> Result<String, MarketDataError> {
+        if let Some(instrument_id) = self.figi_cache.read().await.get(symbol) {
+            return Ok(instrument_id.clone());
+        }
+
+        let response = client
+            .find_instrument(Request::new(InstrumentRequest { ticker: symbol.to_string() }))
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Tinkoff instrument lookup error: {}", e)))?
+            .into_inner();
+
+        if response.instrument_id.is_empty() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
+        }
+
+        self.figi_cache
+            .write()
+            .await
+            .insert(symbol.to_string(), response.instrument_id.clone());
+
+        Ok(response.instrument_id)
+    }
+
+    /// Sends every symbol's instrument id in one `GetLastPrices` call and
+    /// demultiplexes the response back into per-symbol `ModelQuote`s,
+    /// keyed by instrument id so each quote carries the symbol and
+    /// currency it was requested with.
+    async fn fetch_last_prices(
+        &self,
+        symbols_with_currencies: &[(String, String, Option<String>)],
+    ) -> Result<(Vec<ModelQuote>, Vec<(String, String, Option<String>)>), MarketDataError> {
+        let mut client = self.connect().await?;
+
+        let mut instrument_id_to_request: HashMap<String, (String, String, Option<String>)> = HashMap::new();
+        let mut failed = Vec::new();
+
+        for (symbol, currency, exchange) in symbols_with_currencies {
+            match self.resolve_instrument_id(&mut client, symbol).await {
+                Ok(instrument_id) => {
+                    instrument_id_to_request
+                        .insert(instrument_id, (symbol.clone(), currency.clone(), exchange.clone()));
+                }
+                Err(_) => failed.push((symbol.clone(), currency.clone(), exchange.clone())),
+            }
+        }
+
+        if instrument_id_to_request.is_empty() {
+            return Ok((Vec::new(), failed));
+        }
+
+        let response = client
+            .get_last_prices(Request::new(GetLastPricesRequest {
+                instrument_id: instrument_id_to_request.keys().cloned().collect(),
+            }))
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Tinkoff GetLastPrices error: {}", e)))?
+            .into_inner();
+
+        let mut quotes = Vec::new();
+        for last_price in response.last_prices {
+            let Some((symbol, currency, _exchange)) = instrument_id_to_request.remove(&last_price.instrument_id)
+            else {
+                continue;
+            };
+
+            let price = Decimal::from_f64_retain(last_price.price).unwrap_or_default();
+            let timestamp = DateTime::<Utc>::from_timestamp(last_price.timestamp_seconds, 0).unwrap_or_else(Utc::now);
+
+            quotes.push(ModelQuote {
+                id: format!("{}_{}", timestamp.format("%Y%m%d%H%M%S"), symbol),
+                symbol,
+                timestamp,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                adjclose: price,
+                volume: Decimal::ZERO,
+                currency,
+                data_source: DataSource::Tinkoff,
+                created_at: Utc::now(),
+            });
+        }
+
+        // Whatever instrument ids never came back in the response (the
+        // requests map still holds their symbol/currency) are failures.
+        failed.extend(instrument_id_to_request.into_values());
+
+        Ok((quotes, failed))
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for TinkoffProvider {
+    fn name(&self) -> &'static str {
+        "TINKOFF"
+    }
+
+    fn priority(&self) -> u8 {
+        7
+    }
+
+    async fn get_latest_quote(
+        &self,
+        symbol: &str,
+        fallback_currency: String,
+    ) -> Result<ModelQuote, MarketDataError> {
+        let (quotes, _) = self
+            .fetch_last_prices(&[(symbol.to_string(), fallback_currency, None)])
+            .await?;
+        quotes.into_iter().next().ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        symbol: &str,
+        _start: SystemTime,
+        _end: SystemTime,
+        fallback_currency: String,
+    ) -> Result<Vec<ModelQuote>, MarketDataError> {
+        // `GetLastPrices` only ever returns the current price; Tinkoff's
+        // candle history lives behind a separate RPC this provider
+        // doesn't implement, so history just falls back to the latest.
+        self.get_latest_quote(symbol, fallback_currency).await.map(|quote| vec![quote])
+    }
+
+    /// The batched path this provider exists for: one `GetLastPrices` RPC
+    /// for every requested symbol instead of the per-symbol loop other
+    /// providers fall back to.
+    async fn get_historical_quotes_bulk(
+        &self,
+        symbols_with_currencies: &[(String, String, Option<String>)],
+        _start: SystemTime,
+        _end: SystemTime,
+    ) -> Result<(Vec<ModelQuote>, Vec<(String, String, Option<String>)>), MarketDataError> {
+        self.fetch_last_prices(symbols_with_currencies).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_endpoint_url_is_tinkoffs_production_gateway() {
+        assert_eq!(DEFAULT_ENDPOINT_URL, "https://invest-public-api.tinkoff.ru:443");
+    }
+
+    #[test]
+    fn with_config_overrides_the_endpoint_when_base_url_is_set() {
+        let provider = TinkoffProvider::new(DEFAULT_ENDPOINT_URL.to_string()).with_config(&ProviderConfig {
+            base_url: Some("https://sandbox-invest-public-api.tinkoff.ru:443".to_string()),
+            api_token: None,
+            requests_per_minute: None,
+        });
+        assert_eq!(provider.endpoint_url, "https://sandbox-invest-public-api.tinkoff.ru:443");
+    }
+
+    #[test]
+    fn with_config_leaves_the_endpoint_unchanged_without_a_base_url() {
+        let provider = TinkoffProvider::new(DEFAULT_ENDPOINT_URL.to_string())
+            .with_config(&ProviderConfig::default());
+        assert_eq!(provider.endpoint_url, DEFAULT_ENDPOINT_URL);
+    }
+}
+
+/// Hand-written stand-ins for the types `prost`/`tonic-build` would
+/// generate from Tinkoff's `.proto` definitions. A real build compiles
+/// these from the actual `.proto` file via a `build.rs`; this module
+/// exists only so `TinkoffProvider` has something concrete to call.
+mod tinkoff_grpc {
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InstrumentRequest {
+        #[prost(string, tag = "1")]
+        pub ticker: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InstrumentResponse {
+        #[prost(string, tag = "1")]
+        pub instrument_id: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetLastPricesRequest {
+        #[prost(string, repeated, tag = "1")]
+        pub instrument_id: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct GetLastPricesResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub last_prices: Vec<LastPrice>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct LastPrice {
+        #[prost(string, tag = "1")]
+        pub instrument_id: String,
+        #[prost(double, tag = "2")]
+        pub price: f64,
+        #[prost(int64, tag = "3")]
+        pub timestamp_seconds: i64,
+    }
+
+    pub mod market_data_service_client {
+        use tonic::transport::Channel;
+        use tonic::{Request, Response, Status};
+
+        use super::{GetLastPricesRequest, GetLastPricesResponse, InstrumentRequest, InstrumentResponse};
+
+        /// Thin wrapper mirroring the shape of a `tonic-build`-generated
+        /// client. Real generated code drives every call through
+        /// `tonic::client::Grpc`; without the compiled `.proto` this
+        /// simply fails closed with `Status::unimplemented`, since there
+        /// is no real Tinkoff endpoint to reach from this environment.
+        #[derive(Clone)]
+        pub struct MarketDataServiceClient<T> {
+            #[allow(dead_code)]
+            channel: T,
+        }
+
+        impl MarketDataServiceClient<Channel> {
+            pub fn new(channel: Channel) -> Self {
+                Self { channel }
+            }
+
+            pub async fn find_instrument(
+                &mut self,
+                _request: Request<InstrumentRequest>,
+            ) -> Result<Response<InstrumentResponse>, Status> {
+                Err(Status::unimplemented(
+                    "FindInstrument requires the compiled Tinkoff .proto client",
+                ))
+            }
+
+            pub async fn get_last_prices(
+                &mut self,
+                _request: Request<GetLastPricesRequest>,
+            ) -> Result<Response<GetLastPricesResponse>, Status> {
+                Err(Status::unimplemented(
+                    "GetLastPrices requires the compiled Tinkoff .proto client",
+                ))
+            }
+        }
+    }
+}
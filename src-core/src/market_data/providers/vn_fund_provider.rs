@@ -1,5 +1,8 @@
 use async_trait::async_trait;
+use log::error;
 use reqwest::Client;
+use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::SystemTime;
 use crate::market_data::{MarketDataError, Quote as ModelQuote, AssetProfiler, QuoteSummary};
 use crate::market_data::providers::market_data_provider::MarketDataProvider;
@@ -8,18 +11,107 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use crate::market_data::providers::models::AssetProfile;
 use crate::market_data::market_data_model::DataSource;
+use crate::market_data::retry::{retry_with_backoff, RetryConfig};
+use crate::market_data::staleness::{is_outdated_quote, DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS};
 
 const BASE_URL: &str = "http://127.0.0.1:8081";
 
+/// Bound on simultaneous in-flight requests during a bulk history fetch,
+/// so a large symbol list doesn't open an unbounded number of connections.
+const MAX_CONCURRENT_BULK_REQUESTS: usize = 5;
+
 pub struct VnFundProvider {
     client: Client,
+    /// How many trading days old the latest quote is allowed to be before
+    /// `get_latest_quote` rejects it as stale — see
+    /// `VnMarketProvider::with_max_quote_age_trading_days` for why this
+    /// isn't yet wired to a live user-facing setting.
+    max_quote_age_trading_days: u32,
 }
 
 impl VnFundProvider {
     pub fn new() -> Self {
         VnFundProvider {
             client: Client::new(),
+            max_quote_age_trading_days: DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS,
+        }
+    }
+
+    pub fn with_max_quote_age_trading_days(mut self, max_quote_age_trading_days: u32) -> Self {
+        self.max_quote_age_trading_days = max_quote_age_trading_days;
+        self
+    }
+
+    /// Fetches and parses one symbol's history. Takes `client` by reference
+    /// rather than `&self` so it can be called from a spawned task holding
+    /// only a cloned `Client`, without borrowing the provider itself.
+    async fn fetch_history(
+        client: &Client,
+        symbol: &str,
+        fallback_currency: String,
+    ) -> Result<Vec<ModelQuote>, MarketDataError> {
+        let url = format!("{}/history/{}", BASE_URL, symbol);
+
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("VnFund API error: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(MarketDataError::ProviderError(format!("VnFund API error: {}", error_body)));
+        }
+
+        let history_response: HistoryResponse = response
+            .json()
+            .await
+            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse response: {}", e)))?;
+
+        let currency = if history_response.currency.is_empty() {
+            fallback_currency
+        } else {
+            history_response.currency
+        };
+
+        let quotes: Vec<ModelQuote> = history_response
+            .history
+            .into_iter()
+            .filter_map(|item| {
+                let date = NaiveDate::parse_from_str(&item.date, "%Y-%m-%d").ok()?;
+                let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
+
+                let nav = Decimal::from_f64_retain(item.nav).unwrap_or_default();
+                let open = Decimal::from_f64_retain(item.open).unwrap_or(nav);
+                let high = Decimal::from_f64_retain(item.high).unwrap_or(nav);
+                let low = Decimal::from_f64_retain(item.low).unwrap_or(nav);
+                let close = Decimal::from_f64_retain(item.close).unwrap_or(nav);
+                let volume = Decimal::from_f64_retain(item.volume).unwrap_or_default();
+
+                let id = format!("{}_{}", timestamp.format("%Y%m%d"), symbol);
+
+                Some(ModelQuote {
+                    id,
+                    symbol: symbol.to_string(),
+                    timestamp,
+                    open,
+                    high,
+                    low,
+                    close,
+                    adjclose: close,
+                    volume,
+                    currency: currency.clone(),
+                    data_source: DataSource::VnFund,
+                    created_at: Utc::now(),
+                })
+            })
+            .collect();
+
+        if quotes.is_empty() {
+            return Err(MarketDataError::NotFound(symbol.to_string()));
         }
+
+        Ok(quotes)
     }
 }
 
@@ -85,11 +177,26 @@ impl MarketDataProvider for VnFundProvider {
         let start = end - std::time::Duration::from_secs(7 * 24 * 60 * 60);
         
         let quotes = self.get_historical_quotes(symbol, start, end, fallback_currency).await?;
-        
-        quotes
+
+        let latest = quotes
             .into_iter()
             .max_by_key(|q| q.timestamp)
-            .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))
+            .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))?;
+
+        // `MarketDataService` also read-through caches this call (see
+        // `QuoteCache`/`is_outdated_quote`), but guard here too so a direct
+        // caller never receives a quote older than the last completed
+        // trading day just because the cache layer was bypassed.
+        let quote_date = latest.timestamp.date_naive();
+        let today = Utc::now().date_naive();
+        if is_outdated_quote(quote_date, today, self.max_quote_age_trading_days, &HashSet::new()) {
+            return Err(MarketDataError::NotFound(format!(
+                "Latest quote for '{}' is stale (dated {})",
+                symbol, quote_date
+            )));
+        }
+
+        Ok(latest)
     }
 
     async fn get_historical_quotes(
@@ -99,83 +206,55 @@ impl MarketDataProvider for VnFundProvider {
         _end: SystemTime,
         fallback_currency: String,
     ) -> Result<Vec<ModelQuote>, MarketDataError> {
-        let url = format!("{}/history/{}", BASE_URL, symbol);
-        
-        let response = self.client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| MarketDataError::ProviderError(format!("VnFund API error: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error_body = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(MarketDataError::ProviderError(format!("VnFund API error: {}", error_body)));
-        }
-
-        let history_response: HistoryResponse = response
-            .json()
-            .await
-            .map_err(|e| MarketDataError::ProviderError(format!("Failed to parse response: {}", e)))?;
-
-        let currency = if history_response.currency.is_empty() {
-            fallback_currency
-        } else {
-            history_response.currency
-        };
-
-        let quotes: Vec<ModelQuote> = history_response
-            .history
-            .into_iter()
-            .filter_map(|item| {
-                let date = NaiveDate::parse_from_str(&item.date, "%Y-%m-%d").ok()?;
-                let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
-                
-                let nav = Decimal::from_f64_retain(item.nav).unwrap_or_default();
-                let open = Decimal::from_f64_retain(item.open).unwrap_or(nav);
-                let high = Decimal::from_f64_retain(item.high).unwrap_or(nav);
-                let low = Decimal::from_f64_retain(item.low).unwrap_or(nav);
-                let close = Decimal::from_f64_retain(item.close).unwrap_or(nav);
-                let volume = Decimal::from_f64_retain(item.volume).unwrap_or_default();
-
-                let id = format!("{}_{}", timestamp.format("%Y%m%d"), symbol);
-
-                Some(ModelQuote {
-                    id,
-                    symbol: symbol.to_string(),
-                    timestamp,
-                    open,
-                    high,
-                    low,
-                    close,
-                    adjclose: close,
-                    volume,
-                    currency: currency.clone(),
-                    data_source: DataSource::VnFund,
-                    created_at: Utc::now(),
-                })
-            })
-            .collect();
-
-        if quotes.is_empty() {
-            return Err(MarketDataError::NotFound(symbol.to_string()));
-        }
-
-        Ok(quotes)
+        Self::fetch_history(&self.client, symbol, fallback_currency).await
     }
 
     async fn get_historical_quotes_bulk(
         &self,
         symbols_with_currencies: &[(String, String)],
-        start: SystemTime,
-        end: SystemTime,
+        _start: SystemTime,
+        _end: SystemTime,
     ) -> Result<(Vec<ModelQuote>, Vec<(String, String)>), MarketDataError> {
+        let retry_config = RetryConfig {
+            max_attempts: 4,
+            base_delay: std::time::Duration::from_millis(200),
+            max_delay: std::time::Duration::from_secs(5),
+            request_timeout: std::time::Duration::from_secs(20),
+        };
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_BULK_REQUESTS));
+        let mut join_set = tokio::task::JoinSet::new();
+
+        for (symbol, currency) in symbols_with_currencies {
+            let symbol = symbol.clone();
+            let currency = currency.clone();
+            let client = self.client.clone();
+            let semaphore = semaphore.clone();
+            let retry_config = retry_config;
+
+            join_set.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                let result = retry_with_backoff(
+                    &format!("VnFund::get_historical_quotes('{}')", symbol),
+                    &retry_config,
+                    || Self::fetch_history(&client, &symbol, currency.clone()),
+                )
+                .await;
+                (symbol, currency, result)
+            });
+        }
+
         let mut all_quotes = Vec::new();
         let mut failed_symbols = Vec::new();
 
-        for (symbol, currency) in symbols_with_currencies {
-            match self.get_historical_quotes(symbol, start, end, currency.clone()).await {
-                Ok(mut quotes) => all_quotes.append(&mut quotes),
-                Err(_) => failed_symbols.push((symbol.clone(), currency.clone())),
+        while let Some(outcome) = join_set.join_next().await {
+            match outcome {
+                Ok((_, _, Ok(mut quotes))) => all_quotes.append(&mut quotes),
+                Ok((symbol, currency, Err(_))) => failed_symbols.push((symbol, currency)),
+                Err(e) => error!("VnFund bulk history task panicked: {}", e),
             }
         }
 
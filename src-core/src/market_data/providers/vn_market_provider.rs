@@ -1,18 +1,28 @@
 use async_trait::async_trait;
 use reqwest::Client;
+use std::collections::HashSet;
 use std::time::SystemTime;
 use crate::market_data::{MarketDataError, Quote as ModelQuote, AssetProfiler, QuoteSummary};
 use crate::market_data::providers::market_data_provider::MarketDataProvider;
 use chrono::{Utc, NaiveDate, TimeZone};
 use rust_decimal::Decimal;
+use serde::de::{self, Deserializer};
 use serde::Deserialize;
 use crate::market_data::providers::models::AssetProfile;
 use crate::market_data::market_data_model::DataSource;
+use crate::market_data::staleness::{is_outdated_quote, DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS};
 
 const BASE_URL: &str = "http://127.0.0.1:8765";
 
 pub struct VnMarketProvider {
     client: Client,
+    base_url: String,
+    /// How many trading days old the latest quote is allowed to be before
+    /// `get_latest_quote` rejects it as stale. Configurable per instance
+    /// (see `with_max_quote_age_trading_days`) instead of hardcoding
+    /// `DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS`, since how stale a VN Market
+    /// NAV can be before it's untrustworthy is a deployment-specific call.
+    max_quote_age_trading_days: u32,
 }
 
 impl VnMarketProvider {
@@ -22,9 +32,30 @@ impl VnMarketProvider {
                 .no_proxy()
                 .build()
                 .unwrap_or_else(|_| Client::new()),
+            base_url: BASE_URL.to_string(),
+            max_quote_age_trading_days: DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS,
         }
     }
 
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Overrides the freshness allowance `get_latest_quote` enforces.
+    ///
+    /// This doesn't yet surface as a user-facing setting: doing so needs a
+    /// `SettingsServiceTrait` getter and, ideally, a dedicated
+    /// `MarketDataError::StaleQuote` variant so callers can distinguish "no
+    /// quote" from "quote exists but is too old" — both of those live in
+    /// modules outside this one, so for now this only makes the threshold
+    /// configurable at the Rust API level; `ProviderRegistry::new` still
+    /// constructs this provider with the default.
+    pub fn with_max_quote_age_trading_days(mut self, max_quote_age_trading_days: u32) -> Self {
+        self.max_quote_age_trading_days = max_quote_age_trading_days;
+        self
+    }
+
     /// Normalize symbol by stripping .VN suffix for VN Market Service API calls
     /// Example: "MBB.VN" -> "MBB", "FPT.VN" -> "FPT"
     fn normalize_symbol(symbol: &str) -> &str {
@@ -32,6 +63,67 @@ impl VnMarketProvider {
     }
 }
 
+/// The VN Market service mixes numeric and stringified numbers across its
+/// endpoints depending on the upstream data vendor, so fields that should
+/// be a `Decimal` accept either representation on the wire.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(f64),
+    Text(String),
+}
+
+fn decimal_from_number_or_string(value: NumberOrString) -> Result<Decimal, String> {
+    match value {
+        // Parse the shortest decimal text that round-trips the `f64` (e.g.
+        // `0.1`), not the float's full binary expansion.
+        NumberOrString::Number(n) => n
+            .to_string()
+            .parse::<Decimal>()
+            .map_err(|e| format!("'{}' cannot be represented as a Decimal: {}", n, e)),
+        NumberOrString::Text(s) => s
+            .trim()
+            .parse::<Decimal>()
+            .map_err(|e| format!("invalid decimal string '{}': {}", s, e)),
+    }
+}
+
+/// `deserialize_with` helper that accepts a JSON number or a numeric
+/// string and coerces it into a `Decimal`, preserving precision instead of
+/// round-tripping through `f64::from_f64_retain(...).unwrap_or_default()`.
+fn string_or_number_as_decimal<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = NumberOrString::deserialize(deserializer)?;
+    decimal_from_number_or_string(value).map_err(de::Error::custom)
+}
+
+/// Same as [`string_or_number_as_decimal`] but for fields the upstream may
+/// omit entirely.
+fn optional_decimal<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<NumberOrString> = Option::deserialize(deserializer)?;
+    value.map(decimal_from_number_or_string).transpose().map_err(de::Error::custom)
+}
+
+/// `deserialize_with` helper tolerating the handful of date formats the
+/// VN Market service has been observed to send, rather than failing the
+/// whole response on a single unexpected layout.
+fn flexible_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    const FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%d-%m-%Y", "%d/%m/%Y"];
+    let raw = String::deserialize(deserializer)?;
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(&raw, format).ok())
+        .ok_or_else(|| de::Error::custom(format!("unrecognized date format: '{}'", raw)))
+}
+
 #[derive(Debug, Deserialize)]
 struct HistoryResponse {
     symbol: String,
@@ -41,13 +133,20 @@ struct HistoryResponse {
 
 #[derive(Debug, Deserialize)]
 struct HistoryItem {
-    date: String,
-    nav: f64,
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
+    #[serde(deserialize_with = "flexible_date")]
+    date: NaiveDate,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    nav: Decimal,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    open: Decimal,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    high: Decimal,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    low: Decimal,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    close: Decimal,
+    #[serde(deserialize_with = "string_or_number_as_decimal")]
+    volume: Decimal,
 }
 
 #[derive(Debug, Deserialize)]
@@ -57,7 +156,8 @@ struct SearchResponse {
     fund_type: Option<String>,
     management_company: Option<String>,
     inception_date: Option<String>,
-    nav_per_unit: Option<f64>,
+    #[serde(default, deserialize_with = "optional_decimal")]
+    nav_per_unit: Option<Decimal>,
     currency: String,
 }
 
@@ -108,13 +208,27 @@ impl MarketDataProvider for VnMarketProvider {
     ) -> Result<ModelQuote, MarketDataError> {
         let end = SystemTime::now();
         let start = end - std::time::Duration::from_secs(7 * 24 * 60 * 60);
-        
+
         let quotes = self.get_historical_quotes(symbol, start, end, fallback_currency).await?;
-        
-        quotes
+
+        let latest = quotes
             .into_iter()
             .max_by_key(|q| q.timestamp)
-            .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))
+            .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))?;
+
+        // Mutual fund NAVs only update end-of-day, so a few trading days of
+        // slack is normal; beyond that the VN Market service is likely down
+        // and silently handing back a stale NAV would be worse than erroring.
+        let quote_date = latest.timestamp.date_naive();
+        let today = Utc::now().date_naive();
+        if is_outdated_quote(quote_date, today, self.max_quote_age_trading_days, &HashSet::new()) {
+            return Err(MarketDataError::NotFound(format!(
+                "{} has no quote newer than {} trading day(s) old (latest is {})",
+                symbol, self.max_quote_age_trading_days, quote_date
+            )));
+        }
+
+        Ok(latest)
     }
 
     async fn get_historical_quotes(
@@ -125,7 +239,7 @@ impl MarketDataProvider for VnMarketProvider {
         fallback_currency: String,
     ) -> Result<Vec<ModelQuote>, MarketDataError> {
         let normalized_symbol = Self::normalize_symbol(symbol);
-        let url = format!("{}/history/{}", BASE_URL, normalized_symbol);
+        let url = format!("{}/history/{}", self.base_url, normalized_symbol);
         
         let response = self.client
             .get(&url)
@@ -153,28 +267,19 @@ impl MarketDataProvider for VnMarketProvider {
             .history
             .into_iter()
             .filter_map(|item| {
-                let date = NaiveDate::parse_from_str(&item.date, "%Y-%m-%d").ok()?;
-                let timestamp = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
-                
-                let nav = Decimal::from_f64_retain(item.nav).unwrap_or_default();
-                let open = Decimal::from_f64_retain(item.open).unwrap_or(nav);
-                let high = Decimal::from_f64_retain(item.high).unwrap_or(nav);
-                let low = Decimal::from_f64_retain(item.low).unwrap_or(nav);
-                let close = Decimal::from_f64_retain(item.close).unwrap_or(nav);
-                let volume = Decimal::from_f64_retain(item.volume).unwrap_or_default();
-
+                let timestamp = Utc.from_utc_datetime(&item.date.and_hms_opt(0, 0, 0)?);
                 let id = format!("{}_{}", timestamp.format("%Y%m%d"), symbol);
 
                 Some(ModelQuote {
                     id,
                     symbol: symbol.to_string(),
                     timestamp,
-                    open,
-                    high,
-                    low,
-                    close,
-                    adjclose: close,
-                    volume,
+                    open: item.open,
+                    high: item.high,
+                    low: item.low,
+                    close: item.close,
+                    adjclose: item.close,
+                    volume: item.volume,
                     currency: currency.clone(),
                     data_source: DataSource::VnMarket,
                     created_at: Utc::now(),
@@ -214,7 +319,7 @@ impl AssetProfiler for VnMarketProvider {
     async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile, MarketDataError> {
         let normalized_symbol = Self::normalize_symbol(symbol);
         // Use unified search endpoint to get proper asset_type information
-        let url = format!("{}/search?query={}", BASE_URL, normalized_symbol);
+        let url = format!("{}/search?query={}", self.base_url, normalized_symbol);
         
         let response = self.client
             .get(&url)
@@ -301,7 +406,7 @@ impl AssetProfiler for VnMarketProvider {
             return Ok(Vec::new());
         }
         
-        let url = format!("{}/search", BASE_URL);
+        let url = format!("{}/search", self.base_url);
         
         let response = self.client
             .get(&url)
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+
+use super::market_data_model::{DataSource, Quote, QuoteRequest};
+use super::staleness::is_outdated_quote;
+
+/// Synthetic forex symbols are suffixed `=X` (see `QuoteQuery::symbol`);
+/// unlike an equity/fund, a currency pair trades continuously, so it's
+/// freshness-checked against `ttl` rather than "today's trading day".
+const FOREX_SYMBOL_SUFFIX: &str = "=X";
+
+/// Default time-to-live for a cached forex (`=X`) entry before it is
+/// considered stale and must be refetched from the provider. Other entries
+/// are judged by trading day instead (see [`QuoteCache::get_fresh`]).
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// A cache key identifying a single instrument as seen by a specific
+/// provider, mirroring how the `investments` crate keys its quote cache.
+type CacheKey = (String, DataSource);
+
+struct CacheEntry {
+    quote: Quote,
+    fetched_at: SystemTime,
+}
+
+/// Coalescing cache + batching layer sitting in front of the provider
+/// registry. Callers consult the cache first; cache misses are folded into
+/// a per-provider batch so that a single `flush()` issues one grouped
+/// request per provider instead of one request per symbol.
+pub struct QuoteCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<CacheKey, CacheEntry>>,
+    batched_requests: Mutex<HashMap<String, Vec<QuoteRequest>>>,
+}
+
+impl QuoteCache {
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_CACHE_TTL)
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            batched_requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns a cached quote for `(symbol, source)` if present and still
+    /// fresh. A currency pair (synthetic `=X` symbol) trades continuously,
+    /// so it's freshness-checked against the configured TTL; anything else
+    /// is only fresh through the end of its trading day, using the same
+    /// weekend-aware rule as [`is_outdated_quote`] with an empty holiday
+    /// set, so exchange holidays are not skipped. A "latest"
+    /// query must never be answered with a stale entry.
+    pub async fn get_fresh(&self, symbol: &str, source: &DataSource) -> Option<Quote> {
+        let entries = self.entries.lock().await;
+        entries
+            .get(&(symbol.to_string(), source.clone()))
+            .filter(|entry| self.is_fresh(symbol, entry))
+            .map(|entry| entry.quote.clone())
+    }
+
+    fn is_fresh(&self, symbol: &str, entry: &CacheEntry) -> bool {
+        if symbol.ends_with(FOREX_SYMBOL_SUFFIX) {
+            return entry.fetched_at.elapsed().unwrap_or(self.ttl) < self.ttl;
+        }
+        let quote_date = entry.quote.timestamp.date_naive();
+        let today = Utc::now().date_naive();
+        !is_outdated_quote(quote_date, today, 0, &Default::default())
+    }
+
+    /// Enqueues a cache-miss request into the batch for its provider,
+    /// deduping identical `(symbol, source)` requests already pending.
+    pub async fn enqueue(&self, provider_id: &str, request: QuoteRequest) {
+        let mut batches = self.batched_requests.lock().await;
+        let pending = batches.entry(provider_id.to_string()).or_default();
+        if !pending
+            .iter()
+            .any(|existing| existing.symbol == request.symbol && existing.data_source == request.data_source)
+        {
+            pending.push(request);
+        }
+    }
+
+    /// Drains the batched requests, grouped by provider id, so the caller
+    /// can dispatch exactly one grouped request per provider.
+    pub async fn take_batches(&self) -> HashMap<String, Vec<QuoteRequest>> {
+        let mut batches = self.batched_requests.lock().await;
+        std::mem::take(&mut *batches)
+    }
+
+    /// Splices freshly fetched quotes back into the cache.
+    pub async fn store(&self, quotes: &[Quote]) {
+        let mut entries = self.entries.lock().await;
+        let now = SystemTime::now();
+        for quote in quotes {
+            entries.insert(
+                (quote.symbol.clone(), quote.data_source.clone()),
+                CacheEntry {
+                    quote: quote.clone(),
+                    fetched_at: now,
+                },
+            );
+        }
+    }
+
+    /// Evicts a single symbol/source entry. Called whenever a quote is
+    /// saved or imported manually, so the manually entered price wins
+    /// immediately instead of waiting out the TTL.
+    pub async fn evict(&self, symbol: &str, source: &DataSource) {
+        self.entries
+            .lock()
+            .await
+            .remove(&(symbol.to_string(), source.clone()));
+    }
+
+    /// Evicts every cached entry for `symbol` regardless of which provider
+    /// it was fetched from. Unlike `evict`, the caller doesn't need to know
+    /// which `DataSource` currently holds the entry. Backs
+    /// `MarketDataService::invalidate_quote_cache`.
+    pub async fn invalidate_symbol(&self, symbol: &str) {
+        self.entries
+            .lock()
+            .await
+            .retain(|(cached_symbol, _), _| cached_symbol != symbol);
+    }
+}
+
+impl Default for QuoteCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn quote(symbol: &str, timestamp: chrono::DateTime<Utc>) -> Quote {
+        Quote {
+            id: format!("{}_{}", symbol, timestamp.format("%Y%m%d%H%M%S")),
+            symbol: symbol.to_string(),
+            timestamp,
+            open: Decimal::ONE,
+            high: Decimal::ONE,
+            low: Decimal::ONE,
+            close: Decimal::ONE,
+            adjclose: Decimal::ONE,
+            volume: Decimal::ZERO,
+            currency: "USD".to_string(),
+            data_source: DataSource::Manual,
+            created_at: timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn equity_quote_dated_today_is_fresh() {
+        let cache = QuoteCache::new();
+        let q = quote("AAPL", Utc::now());
+        cache.store(&[q.clone()]).await;
+
+        assert!(cache.get_fresh("AAPL", &DataSource::Manual).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn equity_quote_dated_a_week_ago_is_stale() {
+        // A week back is always before the last trading day, whatever
+        // weekday the test runs on.
+        let cache = QuoteCache::new();
+        let q = quote("AAPL", Utc::now() - chrono::Duration::days(7));
+        cache.store(&[q]).await;
+
+        assert!(cache.get_fresh("AAPL", &DataSource::Manual).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn forex_quote_is_fresh_until_ttl_elapses() {
+        // A near-zero TTL means the entry is already stale by the time
+        // we check it, regardless of the quote's own timestamp.
+        let cache = QuoteCache::with_ttl(Duration::from_millis(0));
+        let q = quote("USDEUR=X", Utc::now());
+        cache.store(&[q]).await;
+
+        assert!(cache.get_fresh("USDEUR=X", &DataSource::Manual).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn forex_quote_within_ttl_is_fresh_even_if_dated_yesterday() {
+        let cache = QuoteCache::with_ttl(Duration::from_secs(15 * 60));
+        let q = quote("USDEUR=X", Utc::now() - chrono::Duration::days(1));
+        cache.store(&[q]).await;
+
+        assert!(cache.get_fresh("USDEUR=X", &DataSource::Manual).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn evict_removes_only_the_matching_source() {
+        let cache = QuoteCache::new();
+        cache.store(&[quote("AAPL", Utc::now())]).await;
+        cache.evict("AAPL", &DataSource::Yahoo).await;
+
+        // Evicting a different source than the one stored under is a no-op.
+        assert!(cache.get_fresh("AAPL", &DataSource::Manual).await.is_some());
+
+        cache.evict("AAPL", &DataSource::Manual).await;
+        assert!(cache.get_fresh("AAPL", &DataSource::Manual).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn invalidate_symbol_removes_every_source_for_that_symbol() {
+        let cache = QuoteCache::new();
+        let mut yahoo_quote = quote("AAPL", Utc::now());
+        yahoo_quote.data_source = DataSource::Yahoo;
+        cache.store(&[quote("AAPL", Utc::now()), yahoo_quote]).await;
+        cache.invalidate_symbol("AAPL").await;
+
+        assert!(cache.get_fresh("AAPL", &DataSource::Manual).await.is_none());
+        assert!(cache.get_fresh("AAPL", &DataSource::Yahoo).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn enqueue_dedupes_identical_pending_requests() {
+        let cache = QuoteCache::new();
+        let request = QuoteRequest {
+            symbol: "AAPL".to_string(),
+            currency: "USD".to_string(),
+            data_source: DataSource::Manual,
+        };
+
+        cache.enqueue("YAHOO", request.clone()).await;
+        cache.enqueue("YAHOO", request).await;
+
+        let batches = cache.take_batches().await;
+        assert_eq!(batches.get("YAHOO").map(|reqs| reqs.len()), Some(1));
+    }
+
+    #[tokio::test]
+    async fn take_batches_drains_and_groups_by_provider() {
+        let cache = QuoteCache::new();
+        cache
+            .enqueue(
+                "YAHOO",
+                QuoteRequest { symbol: "AAPL".to_string(), currency: "USD".to_string(), data_source: DataSource::Manual },
+            )
+            .await;
+        cache
+            .enqueue(
+                "KU_COIN",
+                QuoteRequest { symbol: "BTC-USDT".to_string(), currency: "USD".to_string(), data_source: DataSource::Manual },
+            )
+            .await;
+
+        let batches = cache.take_batches().await;
+        assert_eq!(batches.len(), 2);
+        assert!(cache.take_batches().await.is_empty());
+    }
+}
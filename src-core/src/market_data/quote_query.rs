@@ -0,0 +1,50 @@
+/// Distinguishes a bare equity/fund symbol lookup from a currency-pair
+/// lookup, mirroring the `investments` crate's `QuoteQuery` split. Bare
+/// symbol strings are ambiguous between the two (a ticker and a forex pair
+/// look identical to the registry), so callers that know they want an
+/// exchange rate should use `Forex` explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuoteQuery {
+    /// A currency pair, e.g. `Forex("USD".into(), "EUR".into())` for
+    /// USD/EUR.
+    Forex(String, String),
+    /// An equity/fund symbol, optionally restricted to a set of exchanges.
+    Stock(String, Vec<String>),
+}
+
+impl QuoteQuery {
+    /// The synthetic symbol used to key cache/repository lookups for this
+    /// query, e.g. `EURUSD=X` for a forex pair (Yahoo's own convention for
+    /// currency pairs, so the same symbol round-trips through a Yahoo
+    /// fallback if one is registered).
+    pub fn symbol(&self) -> String {
+        match self {
+            QuoteQuery::Forex(base, quote) => format!("{}{}=X", base, quote),
+            QuoteQuery::Stock(symbol, _) => symbol.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forex_symbol_is_base_then_quote_suffixed_with_x() {
+        let query = QuoteQuery::Forex("USD".to_string(), "EUR".to_string());
+        assert_eq!(query.symbol(), "USDEUR=X");
+    }
+
+    #[test]
+    fn stock_symbol_passes_through_unchanged() {
+        let query = QuoteQuery::Stock("AAPL".to_string(), vec!["NASDAQ".to_string()]);
+        assert_eq!(query.symbol(), "AAPL");
+    }
+
+    #[test]
+    fn forex_and_its_inverse_produce_different_symbols() {
+        let base_quote = QuoteQuery::Forex("USD".to_string(), "EUR".to_string());
+        let quote_base = QuoteQuery::Forex("EUR".to_string(), "USD".to_string());
+        assert_ne!(base_quote.symbol(), quote_base.symbol());
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use chrono::NaiveDate;
+use log::{debug, warn};
+use tokio::sync::RwLock;
+
+use super::market_data_model::Quote;
+use super::provider_config::{ProviderConfig, RateLimiterRegistry};
+use super::providers::market_data_provider::MarketDataProvider;
+use super::providers::provider_registry::ProviderRegistry;
+
+/// Caches historical quotes under the `(symbol, source, range)` they were
+/// fetched for. A lookup is served by any cached entry for the same symbol
+/// whose range covers the requested one, whichever source filled it, so
+/// repeated lookups for the same instrument and window don't re-hit the
+/// provider.
+type HistoricalCacheKey = (String, String, NaiveDate, NaiveDate);
+
+/// Sits above the individual providers (`ManualProvider`, `VnMarketProvider`,
+/// ...), ordering them by the registry's configured priority
+/// (`ProviderRegistry::ordered_provider_ids`) and falling through
+/// to the next provider whenever one reports a symbol as failed, batching
+/// pending symbols into a single call per provider instead of one HTTP
+/// request per symbol. Shares its `rate_limiters`/`provider_configs` with
+/// `MarketDataSyncActor` so a provider's configured `requests_per_minute`
+/// is enforced the same way whether a symbol is resolved via a latest-quote
+/// refresh or a historical backfill.
+pub struct QuotesCoordinator {
+    provider_registry: Arc<RwLock<ProviderRegistry>>,
+    provider_configs: Arc<RwLock<HashMap<String, ProviderConfig>>>,
+    rate_limiters: Arc<RateLimiterRegistry>,
+    cache: RwLock<HashMap<HistoricalCacheKey, Vec<Quote>>>,
+}
+
+impl QuotesCoordinator {
+    pub fn new(
+        provider_registry: Arc<RwLock<ProviderRegistry>>,
+        provider_configs: Arc<RwLock<HashMap<String, ProviderConfig>>>,
+        rate_limiters: Arc<RateLimiterRegistry>,
+    ) -> Self {
+        Self {
+            provider_registry,
+            provider_configs,
+            rate_limiters,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Resolves historical quotes for every requested symbol, consulting
+    /// the cache first, then walking providers in priority order and
+    /// passing only the still-unresolved symbols down the chain. Returns
+    /// the aggregated quotes plus the symbols no provider could resolve.
+    pub async fn get_historical_quotes_bulk(
+        &self,
+        symbols_with_currencies: &[(String, String)],
+        start: SystemTime,
+        end: SystemTime,
+    ) -> (Vec<Quote>, Vec<String>) {
+        let start_date = to_naive_date(start);
+        let end_date = to_naive_date(end);
+
+        let mut results = Vec::new();
+        let mut pending: Vec<(String, String)> = Vec::new();
+
+        for (symbol, currency) in symbols_with_currencies {
+            match self.cache_lookup(symbol, start_date, end_date).await {
+                Some(quotes) => results.extend(quotes),
+                None => pending.push((symbol.clone(), currency.clone())),
+            }
+        }
+
+        let provider_ids = self.provider_registry.read().await.ordered_provider_ids();
+
+        for provider_id in provider_ids {
+            if pending.is_empty() {
+                break;
+            }
+
+            let Some(provider) = self.provider_registry.read().await.get_provider(&provider_id).await else {
+                continue;
+            };
+
+            self.rate_limiters.acquire(&provider_id, &self.provider_configs).await;
+
+            let symbols_with_currencies: Vec<(String, String, Option<String>)> = pending
+                .iter()
+                .map(|(symbol, currency)| (symbol.clone(), currency.clone(), None))
+                .collect();
+
+            match provider
+                .get_historical_quotes_bulk(&symbols_with_currencies, start, end)
+                .await
+            {
+                Ok((quotes, failed_symbols)) => {
+                    let failed: HashSet<String> =
+                        failed_symbols.into_iter().map(|(symbol, _, _)| symbol).collect();
+
+                    let mut by_symbol: HashMap<String, Vec<Quote>> = HashMap::new();
+                    for quote in quotes {
+                        by_symbol.entry(quote.symbol.clone()).or_default().push(quote);
+                    }
+
+                    for (symbol, quotes) in &by_symbol {
+                        self.cache_store(symbol, &provider_id, start_date, end_date, quotes.clone()).await;
+                    }
+
+                    results.extend(by_symbol.into_values().flatten());
+                    pending.retain(|(symbol, _)| failed.contains(symbol));
+                }
+                Err(e) => {
+                    warn!("Provider '{}' failed its historical batch entirely: {}", provider_id, e);
+                    // Leave `pending` untouched so the next provider in
+                    // priority order gets a chance at the same symbols.
+                }
+            }
+        }
+
+        debug!(
+            "Quotes coordinator resolved {} quotes, {} symbols unresolved",
+            results.len(),
+            pending.len()
+        );
+
+        (results, pending.into_iter().map(|(symbol, _)| symbol).collect())
+    }
+
+    async fn cache_lookup(&self, symbol: &str, start: NaiveDate, end: NaiveDate) -> Option<Vec<Quote>> {
+        let cache = self.cache.read().await;
+        cache
+            .iter()
+            .find(|((cached_symbol, _, cached_start, cached_end), _)| {
+                cached_symbol == symbol && *cached_start <= start && *cached_end >= end
+            })
+            .map(|(_, quotes)| quotes.clone())
+    }
+
+    async fn cache_store(
+        &self,
+        symbol: &str,
+        source: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+        quotes: Vec<Quote>,
+    ) {
+        self.cache
+            .write()
+            .await
+            .insert((symbol.to_string(), source.to_string(), start, end), quotes);
+    }
+}
+
+fn to_naive_date(time: SystemTime) -> NaiveDate {
+    chrono::DateTime::<chrono::Utc>::from(time).date_naive()
+}
@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+
+/// Selects which stored quote to resolve for a point-in-time lookup,
+/// following the request-time model used by Pyth's Hermes price store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestTime {
+    /// The most recent quote available, regardless of date.
+    Latest,
+    /// The earliest stored quote with `date >= t`.
+    FirstAfter(NaiveDateTime),
+    /// The latest stored quote with `date <= t`.
+    LastBefore(NaiveDateTime),
+}
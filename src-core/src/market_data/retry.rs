@@ -0,0 +1,290 @@
+use std::time::{Duration, SystemTime};
+
+use log::warn;
+
+use super::market_data_errors::MarketDataError;
+
+/// How a classified error should be handled by [`retry_with_backoff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RetryClass {
+    /// Transient - worth retrying (rate limit, timeout, 5xx, connection reset).
+    Retryable,
+    /// Permanent - retrying would just waste attempts (not found, malformed response).
+    Permanent,
+}
+
+/// Tunables for [`retry_with_backoff`]. The backoff after failed attempt
+/// `n` is `base_delay * 2^n` (so the first retry waits twice `base_delay`),
+/// capped at `max_delay`, with a random `0..base_delay` jitter added on top
+/// (the total still capped at `max_delay`) so concurrent callers don't
+/// all retry in lockstep. `request_timeout` bounds each individual attempt.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub request_timeout: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(20),
+        }
+    }
+}
+
+/// Runs `operation` up to `config.max_attempts` times, retrying only errors
+/// classified as transient and backing off exponentially (with jitter)
+/// between attempts. A `Retry-After`-style hint embedded in the error
+/// message, if present, overrides the computed backoff for that attempt.
+/// Permanent errors and exhausted retries both return immediately, the
+/// latter wrapped with the attempt count so it's clear the fetch didn't
+/// just fail once.
+pub async fn retry_with_backoff<T, F, Fut>(
+    operation_name: &str,
+    config: &RetryConfig,
+    mut operation: F,
+) -> Result<T, MarketDataError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, MarketDataError>>,
+{
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+
+        let error = match tokio::time::timeout(config.request_timeout, operation()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(e)) => e,
+            Err(_) => MarketDataError::ProviderError(format!(
+                "{} timed out after {:?}",
+                operation_name, config.request_timeout
+            )),
+        };
+
+        if classify(&error) == RetryClass::Permanent {
+            return Err(error);
+        }
+
+        if attempt >= config.max_attempts {
+            return Err(MarketDataError::ProviderError(format!(
+                "{} failed after {} attempt(s): {}",
+                operation_name, attempt, error
+            )));
+        }
+
+        let delay = retry_after_hint(&error).unwrap_or_else(|| backoff_delay(config, attempt));
+        warn!(
+            "{} failed (attempt {}/{}): {}. Retrying in {:?}",
+            operation_name, attempt, config.max_attempts, error, delay
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Providers surface errors as a plain `MarketDataError::ProviderError`
+/// message rather than a structured HTTP status, so classification is
+/// necessarily a best-effort match against the text a provider produced.
+fn classify(error: &MarketDataError) -> RetryClass {
+    if matches!(error, MarketDataError::NotFound(_) | MarketDataError::UnsupportedProvider(_)) {
+        return RetryClass::Permanent;
+    }
+
+    let message = error.to_string().to_ascii_lowercase();
+    const RETRYABLE_MARKERS: &[&str] = &[
+        "429",
+        "502",
+        "503",
+        "504",
+        "rate limit",
+        "too many requests",
+        "timed out",
+        "timeout",
+        "connection reset",
+        "connection refused",
+        "temporarily unavailable",
+    ];
+    const PERMANENT_MARKERS: &[&str] = &["not found", "malformed", "invalid", "unsupported"];
+
+    if PERMANENT_MARKERS.iter().any(|marker| message.contains(marker)) {
+        RetryClass::Permanent
+    } else if RETRYABLE_MARKERS.iter().any(|marker| message.contains(marker)) {
+        RetryClass::Retryable
+    } else {
+        RetryClass::Permanent
+    }
+}
+
+/// Looks for a `retry-after: <seconds>` / `retry after <seconds>s` hint in
+/// a provider's error message and, if found, honors it verbatim instead of
+/// the computed exponential backoff.
+fn retry_after_hint(error: &MarketDataError) -> Option<Duration> {
+    let message = error.to_string().to_ascii_lowercase();
+    let marker = if let Some(idx) = message.find("retry-after:") {
+        idx + "retry-after:".len()
+    } else if let Some(idx) = message.find("retry after ") {
+        idx + "retry after ".len()
+    } else {
+        return None;
+    };
+
+    let digits: String = message[marker..]
+        .chars()
+        .skip_while(|c| c.is_whitespace())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+
+    digits.parse::<u64>().ok().map(Duration::from_secs)
+}
+
+fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    let exponential = config.base_delay.saturating_mul(1u32 << attempt.min(16));
+    let capped = exponential.min(config.max_delay);
+    capped.saturating_add(jitter(config.base_delay)).min(config.max_delay)
+}
+
+/// A dependency-free stand-in for a random jitter source: the sub-second
+/// fraction of the current time, reduced into `0..base`.
+fn jitter(base: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(0);
+    let base_millis = (base.as_millis().max(1)) as u64;
+    Duration::from_millis(nanos as u64 % base_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn not_found_and_unsupported_are_permanent() {
+        assert_eq!(classify(&MarketDataError::NotFound("AAPL".to_string())), RetryClass::Permanent);
+        assert_eq!(
+            classify(&MarketDataError::UnsupportedProvider("MANUAL".to_string())),
+            RetryClass::Permanent
+        );
+    }
+
+    #[test]
+    fn rate_limit_and_5xx_messages_are_retryable() {
+        for message in ["HTTP 429: rate limit exceeded", "HTTP 503 Service Unavailable", "connection reset by peer"]
+        {
+            let error = MarketDataError::ProviderError(message.to_string());
+            assert_eq!(classify(&error), RetryClass::Retryable, "expected '{}' to be retryable", message);
+        }
+    }
+
+    #[test]
+    fn unrecognized_provider_errors_default_to_permanent() {
+        let error = MarketDataError::ProviderError("something unexpected happened".to_string());
+        assert_eq!(classify(&error), RetryClass::Permanent);
+    }
+
+    #[test]
+    fn permanent_markers_take_priority_over_retryable_ones() {
+        // A message that could match both lists (e.g. mentions "invalid" and
+        // "timeout") should still classify as permanent.
+        let error = MarketDataError::ProviderError("invalid response, client timeout".to_string());
+        assert_eq!(classify(&error), RetryClass::Permanent);
+    }
+
+    #[test]
+    fn retry_after_hint_is_parsed_from_either_phrasing() {
+        let colon_form = MarketDataError::ProviderError("rate limited, retry-after: 42".to_string());
+        assert_eq!(retry_after_hint(&colon_form), Some(Duration::from_secs(42)));
+
+        let prose_form = MarketDataError::ProviderError("please retry after 7s".to_string());
+        assert_eq!(retry_after_hint(&prose_form), Some(Duration::from_secs(7)));
+
+        let no_hint = MarketDataError::ProviderError("service unavailable".to_string());
+        assert_eq!(retry_after_hint(&no_hint), None);
+    }
+
+    #[test]
+    fn backoff_delay_doubles_and_is_capped_at_max_delay() {
+        let config = RetryConfig {
+            max_attempts: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(5),
+        };
+
+        // attempt 1: 100ms * 2^1 = 200ms, plus up-to-100ms jitter, still
+        // comfortably under the 1s cap.
+        let first = backoff_delay(&config, 1);
+        assert!(first >= Duration::from_millis(200) && first < Duration::from_secs(1));
+
+        // A large attempt count must saturate at max_delay rather than
+        // overflow or keep growing.
+        let saturated = backoff_delay(&config, 30);
+        assert_eq!(saturated, Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_gives_up_immediately_on_permanent_errors() {
+        let config = RetryConfig {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            request_timeout: Duration::from_secs(5),
+        };
+
+        let mut calls = 0u32;
+        let result: Result<(), MarketDataError> =
+            retry_with_backoff("permanent_op", &config, || {
+                calls += 1;
+                async { Err(MarketDataError::NotFound("XYZ".to_string())) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_retries_transient_errors_until_exhausted() {
+        let config = RetryConfig {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            request_timeout: Duration::from_secs(5),
+        };
+
+        let mut calls = 0u32;
+        let result: Result<(), MarketDataError> =
+            retry_with_backoff("retryable_op", &config, || {
+                calls += 1;
+                async { Err(MarketDataError::ProviderError("503 Service Unavailable".to_string())) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_returns_the_first_success() {
+        let config = RetryConfig::default();
+        let mut calls = 0u32;
+        let result = retry_with_backoff("flaky_op", &config, || {
+            calls += 1;
+            async move {
+                if calls < 2 {
+                    Err(MarketDataError::ProviderError("503 Service Unavailable".to_string()))
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls, 2);
+    }
+}
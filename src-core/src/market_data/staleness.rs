@@ -0,0 +1,93 @@
+use std::collections::HashSet;
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Default freshness allowance for providers that only publish once per
+/// trading day (e.g. mutual fund NAVs), so an end-of-day update isn't
+/// mistaken for a stale response during the next morning's sync.
+pub const DEFAULT_MAX_QUOTE_AGE_TRADING_DAYS: u32 = 1;
+
+/// Walks `date` back to the nearest trading day on or before it, skipping
+/// weekends and any date in `holidays`.
+fn last_trading_day_on_or_before(date: NaiveDate, holidays: &HashSet<NaiveDate>) -> NaiveDate {
+    let mut day = date;
+    while is_weekend(day) || holidays.contains(&day) {
+        day = day.pred_opt().unwrap_or(day);
+    }
+    day
+}
+
+fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Steps back `count` trading days from `from`, skipping weekends and
+/// `holidays`.
+fn step_back_trading_days(from: NaiveDate, count: u32, holidays: &HashSet<NaiveDate>) -> NaiveDate {
+    let mut day = from;
+    for _ in 0..count {
+        day = day.pred_opt().unwrap_or(day);
+        day = last_trading_day_on_or_before(day, holidays);
+    }
+    day
+}
+
+/// Returns `true` when `quote_date` is older than the freshest trading day
+/// a caller should accept, given `now` and an allowance of
+/// `max_trading_days_old` trading days (0 means "must be dated the most
+/// recent trading day"). `holidays` lets a provider exclude dates it knows
+/// the market was closed beyond ordinary weekends.
+pub fn is_outdated_quote(
+    quote_date: NaiveDate,
+    now: NaiveDate,
+    max_trading_days_old: u32,
+    holidays: &HashSet<NaiveDate>,
+) -> bool {
+    let most_recent_expected = last_trading_day_on_or_before(now, holidays);
+    let oldest_acceptable = step_back_trading_days(most_recent_expected, max_trading_days_old, holidays);
+    quote_date < oldest_acceptable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn friday_quote_is_fresh_on_monday_with_one_trading_day_allowance() {
+        let friday = date("2026-07-24");
+        let monday = date("2026-07-27");
+        assert!(!is_outdated_quote(friday, monday, 1, &HashSet::new()));
+    }
+
+    #[test]
+    fn friday_quote_is_outdated_on_monday_with_zero_allowance() {
+        let friday = date("2026-07-24");
+        let monday = date("2026-07-27");
+        assert!(is_outdated_quote(friday, monday, 0, &HashSet::new()));
+    }
+
+    #[test]
+    fn quote_dated_today_is_never_outdated() {
+        let monday = date("2026-07-27");
+        assert!(!is_outdated_quote(monday, monday, 0, &HashSet::new()));
+    }
+
+    #[test]
+    fn holiday_extends_the_allowance_window() {
+        // Quoting Monday 7/27 as "now" with Friday 7/24 marked a holiday:
+        // the most recent trading day on/before "now" is still Monday, but
+        // stepping back one trading day must skip the holiday and land on
+        // Thursday 7/23, not Friday.
+        let monday = date("2026-07-27");
+        let thursday = date("2026-07-23");
+        let wednesday = date("2026-07-22");
+        let holidays = HashSet::from([date("2026-07-24")]);
+
+        assert!(!is_outdated_quote(thursday, monday, 1, &holidays));
+        assert!(is_outdated_quote(wednesday, monday, 1, &holidays));
+    }
+}
@@ -7,7 +7,9 @@ use crate::{
 
 use log::{debug, error};
 use tauri::{AppHandle, State};
-use wealthfolio_core::market_data::{MarketDataProviderInfo, Quote, QuoteImport, QuoteSummary};
+use wealthfolio_core::market_data::{
+    MarketDataProviderInfo, ProviderConfig, Quote, QuoteImport, QuoteSummary, SyncJob, SyncJobOutcome,
+};
 
 #[tauri::command]
 pub async fn search_symbol(
@@ -115,6 +117,41 @@ pub async fn get_market_data_providers(
         })
 }
 
+#[tauri::command]
+pub async fn get_latest_exchange_rate(
+    base: String,
+    quote: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<String, String> {
+    debug!("Fetching latest exchange rate for {}/{}", base, quote);
+    state
+        .market_data_service()
+        .get_exchange_rate(&base, &quote, None)
+        .await
+        .map(|rate| rate.to_string())
+        .map_err(|e| {
+            error!("Failed to fetch exchange rate for {}/{}: {}", base, quote, e);
+            e.to_string()
+        })
+}
+
+#[tauri::command]
+pub async fn set_market_data_provider_config(
+    provider_id: String,
+    config: ProviderConfig,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Setting market data provider config for {}", provider_id);
+    state
+        .market_data_service()
+        .set_provider_config(provider_id, config)
+        .await
+        .map_err(|e| {
+            error!("Failed to set market data provider config: {}", e);
+            e.to_string()
+        })
+}
+
 #[tauri::command]
 pub async fn import_quotes_csv(
     quotes: Vec<QuoteImport>,
@@ -166,6 +203,22 @@ pub async fn validate_quotes_csv(
     Ok(quotes)
 }
 
+#[tauri::command]
+pub async fn enqueue_market_data_sync_job(
+    job: SyncJob,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SyncJobOutcome, String> {
+    debug!("Enqueuing market data sync job: {:?}", job);
+    state
+        .market_data_service()
+        .enqueue_sync_job(job)
+        .await
+        .map_err(|e| {
+            error!("Market data sync job failed: {}", e);
+            e.to_string()
+        })
+}
+
 #[tauri::command]
 pub async fn get_quote_import_template() -> Result<String, String> {
     debug!("Generating quote import template");
@@ -1,5 +1,5 @@
 use tauri::State;
-use wealthfolio_core::market_data::MarketDataProviderSetting;
+use wealthfolio_core::market_data::{MarketDataProviderSetting, ProviderHealth};
 
 use crate::context::ServiceContext; // To access the service
 use std::sync::Arc;
@@ -46,3 +46,10 @@ pub async fn update_market_data_provider_settings(
         .find(|s| s.id == provider_id)
         .ok_or_else(|| CommandError::ServiceError("Provider setting not found after update".to_string()))
 }
+
+#[tauri::command]
+pub async fn verify_market_data_providers(
+    context: State<'_, Arc<ServiceContext>>,
+) -> CommandResult<Vec<ProviderHealth>> {
+    Ok(context.market_data_service.verify_providers().await?)
+}